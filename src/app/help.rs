@@ -12,7 +12,7 @@ use app::usage;
 use app::{App, AppSettings};
 use args::{AnyArg, ArgSettings, DispOrder};
 use errors::{Error, Result as ClapResult};
-use fmt::{Colorizer, ColorizerOption, Format};
+use fmt::{ColorWhen, Colorizer, ColorizerOption};
 use map::VecMap;
 use INTERNAL_ERROR_MSG;
 
@@ -93,6 +93,10 @@ pub struct Help<'a> {
     longest: usize,
     force_next_line: bool,
     use_long: bool,
+    indent: usize,
+    // extra room given to positional args sharing a section with flags or options, which take up
+    // a column for their short switch
+    pos_pad: usize,
 }
 
 // Public Functions
@@ -133,6 +137,8 @@ impl<'a> Help<'a> {
             longest: 0,
             force_next_line: false,
             use_long: use_long,
+            indent: TAB.len(),
+            pos_pad: 0,
         }
     }
 
@@ -140,14 +146,17 @@ impl<'a> Help<'a> {
     /// and write its help to the wrapped stream.
     pub fn write_app_help(w: &'a mut Write, app: &App, use_long: bool) -> ClapResult<()> {
         debugln!("Help::write_app_help;");
-        Self::write_parser_help(w, &app.p, use_long)
+        // Help which was asked for directly (rather than with --help) isn't headed for the chat,
+        // so it stays plain when only the chat is meant to be colored
+        let color = !app.p.meta.chat_color || app.p.is_set(AppSettings::ColorAlways);
+        Self::_write_parser_help(w, &app.p, false, use_long, color)
     }
 
     /// Reads help settings from a Parser
     /// and write its help to the wrapped stream.
     pub fn write_parser_help(w: &'a mut Write, parser: &Parser, use_long: bool) -> ClapResult<()> {
         debugln!("Help::write_parser_help;");
-        Self::_write_parser_help(w, parser, false, use_long)
+        Self::_write_parser_help(w, parser, false, use_long, true)
     }
 
     /// Reads help settings from a Parser
@@ -155,7 +164,7 @@ impl<'a> Help<'a> {
     /// formatting when required.
     pub fn write_parser_help_to_stderr(w: &'a mut Write, parser: &Parser) -> ClapResult<()> {
         debugln!("Help::write_parser_help;");
-        Self::_write_parser_help(w, parser, true, false)
+        Self::_write_parser_help(w, parser, true, false, true)
     }
 
     #[doc(hidden)]
@@ -164,16 +173,21 @@ impl<'a> Help<'a> {
         parser: &Parser,
         stderr: bool,
         use_long: bool,
+        color: bool,
     ) -> ClapResult<()> {
         debugln!("Help::write_parser_help;");
         let nlh = parser.is_set(AppSettings::NextLineHelp);
         let hide_v = parser.is_set(AppSettings::HidePossibleValuesInHelp);
-        let color = parser.is_set(AppSettings::ColoredHelp);
-        let cizer = Colorizer::new(ColorizerOption {
-            use_stderr: stderr,
-            when: parser.color(),
-        });
-        Self::new(
+        let colors = parser.color();
+        let cizer = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: stderr,
+                when: if color { colors.when } else { ColorWhen::Never },
+            },
+            colors.palette,
+        );
+        let color = color && parser.is_set(AppSettings::ColoredHelp);
+        let mut help = Self::new(
             w,
             nlh,
             hide_v,
@@ -182,7 +196,11 @@ impl<'a> Help<'a> {
             parser.meta.term_w,
             parser.meta.max_w,
             use_long,
-        ).write_help(parser)
+        );
+        if let Some(indent) = parser.meta.help_indent {
+            help.indent = indent;
+        }
+        help.write_help(parser)
     }
 
     /// Writes the parser help to the wrapped stream.
@@ -212,7 +230,7 @@ impl<'a> Help<'a> {
         let mut arg_v = Vec::with_capacity(10);
         let use_long = self.use_long;
         for arg in args.filter(|arg| should_show_arg(use_long, *arg)) {
-            if arg.longest_filter() {
+            if arg.longest_filter() && !self.overflows(arg.as_base()) {
                 self.longest = cmp::max(self.longest, str_width(arg.to_string().as_str()));
             }
             arg_v.push(arg)
@@ -246,7 +264,7 @@ impl<'a> Help<'a> {
             // args alignment
             should_show_arg(use_long, *arg)
         }) {
-            if arg.longest_filter() {
+            if arg.longest_filter() && !self.overflows(arg.as_base()) {
                 debugln!("Help::write_args: Current Longest...{}", self.longest);
                 self.longest = cmp::max(self.longest, str_width(arg.to_string().as_str()));
                 debugln!("Help::write_args: New Longest...{}", self.longest);
@@ -268,6 +286,14 @@ impl<'a> Help<'a> {
         Ok(())
     }
 
+    /// Whether an argument is so wide that its help would start past two thirds of the line.
+    /// Such arguments are left out when aligning the help column and get their help on the
+    /// next line instead.
+    fn overflows<'b, 'c>(&self, arg: &ArgWithDisplay<'b, 'c>) -> bool {
+        let taken = str_width(arg.to_string().as_str()) + self.indent + 8;
+        taken > self.term_w / 3 * 2
+    }
+
     /// Writes help for an argument to the wrapped stream.
     fn write_arg<'b, 'c>(&mut self, arg: &ArgWithDisplay<'b, 'c>) -> io::Result<()> {
         debugln!("Help::write_arg;");
@@ -281,7 +307,7 @@ impl<'a> Help<'a> {
     /// Writes argument's short command to the wrapped stream.
     fn short<'b, 'c>(&mut self, arg: &ArgWithDisplay<'b, 'c>) -> io::Result<()> {
         debugln!("Help::short;");
-        write!(self.writer, "{}", TAB)?;
+        write_nspaces!(self.writer, self.indent);
         if let Some(s) = arg.short() {
             color!(self, "-{}", s, good)
         } else if arg.has_switch() {
@@ -366,10 +392,12 @@ impl<'a> Help<'a> {
         let h = arg.help().unwrap_or("");
         let h_w = str_width(h) + str_width(&*spec_vals);
         let nlh = self.next_line_help || arg.is_set(ArgSettings::NextLineHelp);
-        let taken = self.longest + 12;
-        self.force_next_line = !nlh && self.term_w >= taken
-            && (taken as f32 / self.term_w as f32) > 0.40
-            && h_w > (self.term_w - taken);
+        let taken = self.longest + self.indent + 8;
+        let overflow = self.overflows(arg);
+        self.force_next_line = !nlh
+            && (overflow && h_w > 0
+                || self.term_w >= taken && (taken as f32 / self.term_w as f32) > 0.40
+                    && h_w > (self.term_w - taken));
 
         debug!("Help::val: Has switch...");
         if arg.has_switch() {
@@ -385,7 +413,7 @@ impl<'a> Help<'a> {
             );
             debugln!("Help::val: longest...{}", self.longest);
             debug!("Help::val: next_line...");
-            if !(nlh || self.force_next_line) {
+            if !(nlh || self.force_next_line || overflow) {
                 sdebugln!("No");
                 let self_len = str_width(arg.to_string().as_str());
                 // subtract ourself
@@ -404,11 +432,11 @@ impl<'a> Help<'a> {
             } else {
                 sdebugln!("Yes");
             }
-        } else if !(nlh || self.force_next_line) {
+        } else if !(nlh || self.force_next_line || overflow) {
             sdebugln!("No, and not next_line");
             write_nspaces!(
                 self.writer,
-                self.longest + 4 - (str_width(arg.to_string().as_str()))
+                self.longest + 4 + self.pos_pad - (str_width(arg.to_string().as_str()))
             );
         } else {
             sdebugln!("No");
@@ -416,35 +444,63 @@ impl<'a> Help<'a> {
         Ok(spec_vals)
     }
 
+    /// Writes the after help, highlighting example lines (those starting with `$`) when
+    /// `ColoredHelp` is set.
+    fn write_after_help(&mut self, h: &str) -> io::Result<()> {
+        debugln!("Help::write_after_help;");
+        let help = self.wrap_before_after_help(h);
+        self.write_examples(&*help)
+    }
+
+    fn write_examples(&mut self, help: &str) -> io::Result<()> {
+        for (i, line) in help.split('\n').enumerate() {
+            if i != 0 {
+                self.writer.write_all(b"\n")?;
+            }
+            if line.trim_left().starts_with('$') {
+                color!(self, line, good)?;
+            } else {
+                write!(self.writer, "{}", line)?;
+            }
+        }
+        Ok(())
+    }
+
     fn write_before_after_help(&mut self, h: &str) -> io::Result<()> {
         debugln!("Help::write_before_after_help;");
+        let help = self.wrap_before_after_help(h);
+        write!(self.writer, "{}", help)?;
+        Ok(())
+    }
+
+    fn wrap_before_after_help(&self, h: &str) -> String {
+        debugln!("Help::wrap_before_after_help;");
         let mut help = String::from(h);
         // determine if our help fits or needs to wrap
         debugln!(
-            "Help::write_before_after_help: Term width...{}",
+            "Help::wrap_before_after_help: Term width...{}",
             self.term_w
         );
         let too_long = str_width(h) >= self.term_w;
 
-        debug!("Help::write_before_after_help: Too long...");
+        debug!("Help::wrap_before_after_help: Too long...");
         if too_long || h.contains("{n}") {
             sdebugln!("Yes");
-            debugln!("Help::write_before_after_help: help: {}", help);
+            debugln!("Help::wrap_before_after_help: help: {}", help);
             debugln!(
-                "Help::write_before_after_help: help width: {}",
+                "Help::wrap_before_after_help: help width: {}",
                 str_width(&*help)
             );
             // Determine how many newlines we need to insert
             debugln!(
-                "Help::write_before_after_help: Usable space: {}",
+                "Help::wrap_before_after_help: Usable space: {}",
                 self.term_w
             );
             help = wrap_help(&help.replace("{n}", "\n"), self.term_w);
         } else {
             sdebugln!("No");
         }
-        write!(self.writer, "{}", help)?;
-        Ok(())
+        help
     }
 
     /// Writes argument's help to the wrapped stream.
@@ -460,16 +516,17 @@ impl<'a> Help<'a> {
         debugln!("Help::help: Next Line...{:?}", nlh);
 
         let spcs = if nlh || self.force_next_line {
-            12 // "tab" * 3
+            self.indent * 3
         } else {
-            self.longest + 12
+            self.longest + self.indent + 8
         };
 
         let too_long = spcs + str_width(h) + str_width(&*spec_vals) >= self.term_w;
 
         // Is help on next line, if so then indent
         if nlh || self.force_next_line {
-            write!(self.writer, "\n{}{}{}", TAB, TAB, TAB)?;
+            write!(self.writer, "\n")?;
+            write_nspaces!(self.writer, self.indent * 3);
         }
 
         debug!("Help::help: Too long...");
@@ -490,11 +547,11 @@ impl<'a> Help<'a> {
         for part in help.lines().skip(1) {
             write!(self.writer, "\n")?;
             if nlh || self.force_next_line {
-                write!(self.writer, "{}{}{}", TAB, TAB, TAB)?;
+                write_nspaces!(self.writer, self.indent * 3);
             } else if arg.has_switch() {
-                write_nspaces!(self.writer, self.longest + 12);
+                write_nspaces!(self.writer, self.longest + self.indent + 8);
             } else {
-                write_nspaces!(self.writer, self.longest + 8);
+                write_nspaces!(self.writer, self.longest + self.indent + 4);
             }
             write!(self.writer, "{}", part)?;
         }
@@ -532,7 +589,7 @@ impl<'a> Help<'a> {
                     if self.color {
                         self.cizer.good(pv.to_string_lossy())
                     } else {
-                        Format::None(pv.to_string_lossy())
+                        self.cizer.none(pv.to_string_lossy())
                     }
                 ));
             }
@@ -590,12 +647,14 @@ impl<'a> Help<'a> {
     #[cfg_attr(feature = "cargo-clippy", allow(useless_let_if_seq))]
     pub fn write_all_args(&mut self, parser: &Parser) -> ClapResult<()> {
         debugln!("Help::write_all_args;");
-        let flags = parser.has_flags();
+        // Args with a custom help heading get their own sections further down
+        let flags = parser.flags().any(|f| f.b.help_heading.is_none());
         let pos = parser
             .positionals()
+            .filter(|arg| arg.b.help_heading.is_none())
             .filter(|arg| !arg.is_set(ArgSettings::Hidden))
             .count() > 0;
-        let opts = parser.has_opts();
+        let opts = parser.opts().any(|o| o.b.help_heading.is_none());
         let subcmds = parser.has_visible_subcommands();
 
         let unified_help = parser.is_set(AppSettings::UnifiedHelpMessage);
@@ -605,23 +664,34 @@ impl<'a> Help<'a> {
         if unified_help && (flags || opts) {
             let opts_flags = parser
                 .flags()
+                .filter(|f| f.b.help_heading.is_none())
                 .map(as_arg_trait)
-                .chain(parser.opts().map(as_arg_trait));
-            color!(self, "OPTIONS:\n", warning)?;
+                .chain(parser.opts().filter(|o| o.b.help_heading.is_none()).map(as_arg_trait));
+            color!(self, "{}:\n", parser.meta.opts_heading.unwrap_or("OPTIONS"), warning)?;
             self.write_args(opts_flags)?;
             first = false;
         } else {
             if flags {
-                color!(self, "FLAGS:\n", warning)?;
-                self.write_args(parser.flags().map(as_arg_trait))?;
+                color!(self, "{}:\n", parser.meta.flags_heading.unwrap_or("FLAGS"), warning)?;
+                self.write_args(
+                    parser
+                        .flags()
+                        .filter(|f| f.b.help_heading.is_none())
+                        .map(as_arg_trait),
+                )?;
                 first = false;
             }
             if opts {
                 if !first {
                     self.writer.write_all(b"\n\n")?;
                 }
-                color!(self, "OPTIONS:\n", warning)?;
-                self.write_args(parser.opts().map(as_arg_trait))?;
+                color!(self, "{}:\n", parser.meta.opts_heading.unwrap_or("OPTIONS"), warning)?;
+                self.write_args(
+                    parser
+                        .opts()
+                        .filter(|o| o.b.help_heading.is_none())
+                        .map(as_arg_trait),
+                )?;
                 first = false;
             }
         }
@@ -630,8 +700,60 @@ impl<'a> Help<'a> {
             if !first {
                 self.writer.write_all(b"\n\n")?;
             }
-            color!(self, "ARGS:\n", warning)?;
-            self.write_args_unsorted(parser.positionals().map(as_arg_trait))?;
+            color!(self, "{}:\n", parser.meta.pos_heading.unwrap_or("ARGS"), warning)?;
+            self.write_args_unsorted(
+                parser
+                    .positionals()
+                    .filter(|p| p.b.help_heading.is_none())
+                    .map(as_arg_trait),
+            )?;
+            first = false;
+        }
+
+        // Custom headings are listed in the order they were first used, with flags and options
+        // in declaration order coming before positional args
+        let mut switched: Vec<_> = parser
+            .flags()
+            .filter_map(|f| {
+                f.b.help_heading
+                    .map(|h| (h, f.s.disp_ord, f.s.unified_ord, as_arg_trait(f)))
+            })
+            .chain(parser.opts().filter_map(|o| {
+                o.b.help_heading
+                    .map(|h| (h, o.s.disp_ord, o.s.unified_ord, as_arg_trait(o)))
+            }))
+            .collect();
+        switched.sort_by_key(|&(_, _, unified_ord, _)| unified_ord);
+        let positionals: Vec<_> = parser
+            .positionals()
+            .filter_map(|p| p.b.help_heading.map(|h| (h, as_arg_trait(p))))
+            .collect();
+        let mut headings: Vec<&str> = vec![];
+        for h in switched.iter().map(|a| a.0).chain(positionals.iter().map(|a| a.0)) {
+            if !headings.contains(&h) {
+                headings.push(h);
+            }
+        }
+        switched.sort_by_key(|&(_, disp_ord, unified_ord, _)| (disp_ord, unified_ord));
+
+        for heading in headings {
+            let args: Vec<_> = switched
+                .iter()
+                .filter(|a| a.0 == heading)
+                .map(|a| a.3)
+                .chain(positionals.iter().filter(|a| a.0 == heading).map(|a| a.1))
+                .collect();
+            let use_long = self.use_long;
+            if !args.iter().any(|a| should_show_arg(use_long, *a)) {
+                continue;
+            }
+            if !first {
+                self.writer.write_all(b"\n\n")?;
+            }
+            color!(self, "{}:\n", heading, warning)?;
+            self.pos_pad = if args.iter().any(|a| a.has_switch()) { 4 } else { 0 };
+            self.write_args_unsorted(args.into_iter())?;
+            self.pos_pad = 0;
             first = false;
         }
 
@@ -748,12 +870,12 @@ impl<'a> Help<'a> {
         }
 
         color!(self, "\nUSAGE:", warning)?;
-        write!(
-            self.writer,
-            "\n{}{}\n\n",
-            TAB,
-            usage::create_usage_no_title(parser, &[])
-        )?;
+        write!(self.writer, "\n")?;
+        for line in usage::create_usage_no_title(parser, &[]).lines() {
+            write_nspaces!(self.writer, self.indent);
+            write!(self.writer, "{}\n", line.trim_left())?;
+        }
+        write!(self.writer, "\n")?;
 
         let flags = parser.has_flags();
         let pos = parser.has_positionals();
@@ -768,7 +890,7 @@ impl<'a> Help<'a> {
             if flags || opts || pos || subcmds {
                 self.writer.write_all(b"\n\n")?;
             }
-            self.write_before_after_help(h)?;
+            self.write_after_help(h)?;
         }
 
         self.writer.flush().map_err(Error::from)
@@ -982,11 +1104,7 @@ impl<'a> Help<'a> {
                     self.write_subcommands(parser)?;
                 }
                 b"after-help" => {
-                    write!(
-                        self.writer,
-                        "{}",
-                        parser.meta.more_help.unwrap_or("unknown after-help")
-                    )?;
+                    self.write_examples(parser.meta.more_help.unwrap_or("unknown after-help"))?;
                 }
                 b"before-help" => {
                     write!(
@@ -1007,7 +1125,10 @@ impl<'a> Help<'a> {
 }
 
 fn wrap_help(help: &str, avail_chars: usize) -> String {
-    let wrapper = textwrap::Wrapper::new(avail_chars).break_words(false);
+    // Words are never broken, so a line can't be narrower than the widest word anyway. Wrapping
+    // to less than that also trips up textwrap on multi-byte characters.
+    let widest = help.split_whitespace().map(str_width).max().unwrap_or(0);
+    let wrapper = textwrap::Wrapper::new(cmp::max(avail_chars, widest)).break_words(false);
     help.lines()
         .map(|line| wrapper.fill(line))
         .collect::<Vec<String>>()
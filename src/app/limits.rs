@@ -0,0 +1,101 @@
+// Std
+use std::ffi::{OsStr, OsString};
+
+// Internal
+use errors::{Error, Result as ClapResult};
+use fmt::Colors;
+
+/// Upper bounds applied while parsing, for when the arguments come from an untrusted source such
+/// as a `/command` typed into the ClassiCube chat by any player.
+///
+/// When a limit is exceeded parsing stops immediately with an [`ErrorKind::LimitExceeded`] error,
+/// instead of continuing to collect (and allocate for) the rest of the input. All limits are
+/// unbounded by default.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, ErrorKind, ParseLimits};
+/// let res = App::new("prog")
+///     .parse_limits(ParseLimits::new()
+///         .max_tokens(4)
+///         .max_token_len(32)
+///         .max_values(2))
+///     .arg(Arg::with_name("players")
+///         .multiple(true))
+///     .get_matches_from_safe(vec!["prog", "a", "b", "c"]);
+///
+/// assert!(res.is_err());
+/// assert_eq!(res.unwrap_err().kind, ErrorKind::LimitExceeded);
+/// ```
+/// [`ErrorKind::LimitExceeded`]: ./enum.ErrorKind.html#variant.LimitExceeded
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ParseLimits {
+    #[doc(hidden)]
+    pub max_tokens: Option<usize>,
+    #[doc(hidden)]
+    pub max_token_len: Option<usize>,
+    #[doc(hidden)]
+    pub max_values: Option<usize>,
+}
+
+impl ParseLimits {
+    /// Creates a new set of limits, with nothing limited
+    pub fn new() -> Self { Default::default() }
+
+    /// Sets the maximum number of arguments (not counting the binary name) which will be read
+    pub fn max_tokens(mut self, max: usize) -> Self {
+        self.max_tokens = Some(max);
+        self
+    }
+
+    /// Sets the maximum length, in bytes, of any single argument
+    pub fn max_token_len(mut self, max: usize) -> Self {
+        self.max_token_len = Some(max);
+        self
+    }
+
+    /// Sets the maximum number of values any single argument may collect, including values split
+    /// out by a [`Arg::value_delimiter`]
+    ///
+    /// [`Arg::value_delimiter`]: ./struct.Arg.html#method.value_delimiter
+    pub fn max_values(mut self, max: usize) -> Self {
+        self.max_values = Some(max);
+        self
+    }
+
+    #[doc(hidden)]
+    pub fn limits_input(&self) -> bool { self.max_tokens.is_some() || self.max_token_len.is_some() }
+
+    #[doc(hidden)]
+    pub fn collect<I, T>(&self, itr: I, color: Colors) -> ClapResult<Vec<OsString>>
+    where
+        I: Iterator<Item = T>,
+        T: Into<OsString>,
+    {
+        let mut args = Vec::new();
+        for arg in itr {
+            let arg = arg.into();
+            try!(self.check(&args, &arg, color));
+            args.push(arg);
+        }
+        Ok(args)
+    }
+
+    // Checks that `arg` may be added to the arguments collected so far in `args`, so input can be
+    // rejected while it's being built up rather than after
+    #[doc(hidden)]
+    pub fn check(&self, args: &[OsString], arg: &OsStr, color: Colors) -> ClapResult<()> {
+        if let Some(max) = self.max_tokens {
+            if args.len() >= max {
+                return Err(Error::limit_exceeded("arguments", max, None, color));
+            }
+        }
+        if let Some(max) = self.max_token_len {
+            if arg.len() > max {
+                return Err(Error::limit_exceeded("bytes per argument", max, None, color));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+// Std
+use std::io::{Result, Write};
+
+// Internal
+use app::parser::Parser;
+use app::settings::AppSettings as AS;
+use app::usage;
+use args::{ArgSettings, Base};
+
+pub struct ManGen<'a, 'b>
+where
+    'a: 'b,
+{
+    p: &'b Parser<'a, 'b>,
+}
+
+impl<'a, 'b> ManGen<'a, 'b> {
+    pub fn new(p: &'b Parser<'a, 'b>) -> Self { ManGen { p: p } }
+
+    pub fn generate_to<W: Write>(&self, buf: &mut W) -> Result<()> {
+        debugln!("ManGen::generate_to;");
+        let name = &*self.p.meta.name;
+        try!(writeln!(
+            buf,
+            ".TH {} 1 \"\" \"{}\"",
+            escape(&*name.to_uppercase()),
+            escape(&*format!("{} {}", name, self.p.meta.version.unwrap_or("")).trim())
+        ));
+
+        try!(writeln!(buf, ".SH NAME"));
+        match self.p.meta.about {
+            Some(about) => try!(writeln!(buf, "{} \\- {}", escape(name), escape(about))),
+            None => try!(writeln!(buf, "{}", escape(name))),
+        }
+
+        try!(writeln!(buf, ".SH SYNOPSIS"));
+        try!(writeln!(
+            buf,
+            "{}",
+            escape(&*usage::create_usage_no_title(self.p, &[]))
+        ));
+
+        try!(self.write_options(buf));
+        try!(self.write_args(buf));
+        self.write_subcommands(buf)
+    }
+
+    fn write_options<W: Write>(&self, buf: &mut W) -> Result<()> {
+        debugln!("ManGen::write_options;");
+        let flags = self.p.flags().filter(|f| !f.b.is_set(ArgSettings::Hidden));
+        let opts = self.p.opts().filter(|o| !o.b.is_set(ArgSettings::Hidden));
+        let mut first = true;
+        for f in flags {
+            if first {
+                try!(writeln!(buf, ".SH OPTIONS"));
+                first = false;
+            }
+            try!(writeln!(buf, ".TP"));
+            try!(writeln!(buf, "{}", switches(f.s.short, f.s.long)));
+            try!(write_help(buf, &f.b));
+        }
+        for o in opts {
+            if first {
+                try!(writeln!(buf, ".SH OPTIONS"));
+                first = false;
+            }
+            // The value names are everything after the first separator of the displayed option,
+            // e.g. `<FILE>` in `--config <FILE>`
+            let display = o.to_string();
+            let vals = display.splitn(2, |c| c == ' ' || c == '=').nth(1).unwrap_or("");
+            try!(writeln!(buf, ".TP"));
+            try!(writeln!(
+                buf,
+                "{} \\fI{}\\fR",
+                switches(o.s.short, o.s.long),
+                escape(vals)
+            ));
+            try!(write_help(buf, &o.b));
+        }
+        Ok(())
+    }
+
+    fn write_args<W: Write>(&self, buf: &mut W) -> Result<()> {
+        debugln!("ManGen::write_args;");
+        let mut first = true;
+        for p in self.p.positionals().filter(|p| !p.b.is_set(ArgSettings::Hidden)) {
+            if first {
+                try!(writeln!(buf, ".SH ARGS"));
+                first = false;
+            }
+            try!(writeln!(buf, ".TP"));
+            try!(writeln!(buf, "\\fI{}\\fR", escape(&*p.to_string())));
+            try!(write_help(buf, &p.b));
+        }
+        Ok(())
+    }
+
+    fn write_subcommands<W: Write>(&self, buf: &mut W) -> Result<()> {
+        debugln!("ManGen::write_subcommands;");
+        let mut first = true;
+        for sc in self.p.subcommands.iter().filter(|sc| !sc.p.is_set(AS::Hidden)) {
+            if first {
+                try!(writeln!(buf, ".SH SUBCOMMANDS"));
+                first = false;
+            }
+            try!(writeln!(buf, ".TP"));
+            try!(writeln!(buf, "\\fB{}\\fR", escape(&*sc.p.meta.name)));
+            if let Some(about) = sc.p.meta.long_about.or(sc.p.meta.about) {
+                try!(write_lines(buf, about));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Formats the short and long switches of an argument in bold, e.g. `-c, --config`
+fn switches(short: Option<char>, long: Option<&str>) -> String {
+    let short = short.map(|s| format!("\\fB{}\\fR", escape(&*format!("-{}", s))));
+    let long = long.map(|l| format!("\\fB{}\\fR", escape(&*format!("--{}", l))));
+    match (short, long) {
+        (Some(s), Some(l)) => format!("{}, {}", s, l),
+        (Some(s), None) => s,
+        (None, Some(l)) => l,
+        (None, None) => String::new(),
+    }
+}
+
+fn write_help<W: Write>(buf: &mut W, b: &Base) -> Result<()> {
+    match b.long_help.or(b.help) {
+        Some(help) => write_lines(buf, help),
+        None => Ok(()),
+    }
+}
+
+fn write_lines<W: Write>(buf: &mut W, text: &str) -> Result<()> {
+    for line in text.lines() {
+        try!(writeln!(buf, "{}", escape(line)));
+    }
+    Ok(())
+}
+
+// Escapes the characters roff would otherwise treat specially, and keeps lines from being read as
+// requests
+fn escape(s: &str) -> String {
+    let s = s.replace('\\', "\\e").replace('-', "\\-");
+    if s.starts_with('.') || s.starts_with('\'') {
+        format!("\\&{}", s)
+    } else {
+        s
+    }
+}
@@ -1,3 +1,12 @@
+// Std
+use std::rc::Rc;
+
+// Internal
+use app::limits::ParseLimits;
+use args::ArgMatches;
+use fmt::Palette;
+use output::OutputSink;
+
 #[doc(hidden)]
 #[allow(missing_debug_implementations)]
 #[derive(Default, Clone)]
@@ -13,12 +22,25 @@ pub struct AppMeta<'b> {
     pub pre_help: Option<&'b str>,
     pub aliases: Option<Vec<(&'b str, bool)>>, // (name, visible)
     pub usage_str: Option<&'b str>,
+    pub usage_lines: Vec<&'b str>,
     pub usage: Option<String>,
     pub help_str: Option<&'b str>,
     pub disp_ord: usize,
     pub term_w: Option<usize>,
     pub max_w: Option<usize>,
+    pub help_indent: Option<usize>,
     pub template: Option<&'b str>,
+    pub flags_heading: Option<&'b str>,
+    pub opts_heading: Option<&'b str>,
+    pub pos_heading: Option<&'b str>,
+    pub sink: Option<Rc<OutputSink>>,
+    pub chat_color: bool,
+    pub palette: Option<Palette>,
+    pub limits: ParseLimits,
+    pub replacers: Vec<(&'b str, Vec<&'b str>)>,
+    pub error_exit_code: Option<i32>,
+    pub handlers: Vec<(&'b str, Rc<Fn(&ArgMatches)>)>,
+    pub validators: Vec<Rc<Fn(&ArgMatches) -> Result<(), String>>>,
 }
 
 impl<'b> AppMeta<'b> {
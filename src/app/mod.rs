@@ -4,11 +4,18 @@ mod meta;
 mod help;
 mod validator;
 mod usage;
+mod limits;
+mod man;
+mod response_files;
 
 // Std
+#[allow(deprecated, unused_imports)]
+use std::ascii::AsciiExt;
 use std::ffi::{OsStr, OsString};
 use std::fmt;
 use std::io::{self, BufRead, BufWriter, Write};
+use std::iter;
+use std::mem;
 use std::path::Path;
 use std::process;
 use std::rc::Rc;
@@ -21,11 +28,18 @@ use yaml_rust::Yaml;
 // Internal
 use app::help::Help;
 use app::parser::Parser;
-use args::{AnyArg, Arg, ArgGroup, ArgMatcher, ArgMatches, ArgSettings};
+use args::{AnyArg, Arg, ArgGroup, ArgMatcher, ArgMatches, ArgSettings, TokenKind};
+use errors::Error;
 use errors::Result as ClapResult;
+use fmt::Palette;
 pub use self::settings::AppSettings;
+pub use self::limits::ParseLimits;
 use completions::Shell;
 use map::{self, VecMap};
+use output::{OutputKind, OutputSink};
+use usage_parser::UsageParser;
+use INTERNAL_ERROR_MSG;
+use INVALID_UTF8;
 
 /// Used to create a representation of a command line program and all possible command line
 /// arguments. Application settings are set using the "builder pattern" with the
@@ -88,6 +102,45 @@ impl<'a, 'b> App<'a, 'b> {
     /// Get the name of the binary
     pub fn get_bin_name(&self) -> Option<&str> { self.p.meta.bin_name.as_ref().map(|s| s.as_str()) }
 
+    /// Get the version of the app, if one was set with [`App::version`]
+    ///
+    /// [`App::version`]: ./struct.App.html#method.version
+    pub fn get_version(&self) -> Option<&str> { self.p.meta.version }
+
+    /// Get the author of the app, if one was set with [`App::author`]
+    ///
+    /// [`App::author`]: ./struct.App.html#method.author
+    pub fn get_author(&self) -> Option<&str> { self.p.meta.author }
+
+    /// Get the short description of the app, if one was set with [`App::about`]
+    ///
+    /// [`App::about`]: ./struct.App.html#method.about
+    pub fn get_about(&self) -> Option<&str> { self.p.meta.about }
+
+    /// Get the names of all flags, in the order they were added
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let app = App::new("prog")
+    ///     .arg(Arg::with_name("verbose").short("v"))
+    ///     .arg(Arg::with_name("config").long("config").takes_value(true))
+    ///     .arg(Arg::with_name("input"));
+    /// assert_eq!(app.get_flag_names(), ["verbose"]);
+    /// assert_eq!(app.get_opt_names(), ["config"]);
+    /// assert_eq!(app.get_positional_names(), ["input"]);
+    /// ```
+    pub fn get_flag_names(&self) -> Vec<&'a str> { self.p.flags().map(|f| f.b.name).collect() }
+
+    /// Get the names of all options, in the order they were added
+    pub fn get_opt_names(&self) -> Vec<&'a str> { self.p.opts().map(|o| o.b.name).collect() }
+
+    /// Get the names of all positional arguments, ordered by their index
+    pub fn get_positional_names(&self) -> Vec<&'a str> {
+        self.p.positionals().map(|p| p.b.name).collect()
+    }
+
     /// Creates a new instance of an application requiring a name, but uses the [`crate_authors!`]
     /// and [`crate_version!`] macros to fill in the [`App::author`] and [`App::version`] fields.
     ///
@@ -157,6 +210,8 @@ impl<'a, 'b> App<'a, 'b> {
     /// Sets a string of author(s) that will be displayed to the user when they
     /// request the help information with `--help` or `-h`.
     ///
+    /// Any [`SubCommand`]s which don't set their own author inherit this one.
+    ///
     /// **Pro-tip:** Use `clap`s convenience macro [`crate_authors!`] to automatically set your
     /// application's author(s) to the same thing as your crate at compile time. See the [`examples/`]
     /// directory for more information
@@ -174,6 +229,7 @@ impl<'a, 'b> App<'a, 'b> {
     /// ```
     /// [`crate_authors!`]: ./macro.crate_authors!.html
     /// [`examples/`]: https://github.com/clap-rs/clap/tree/master/examples
+    /// [`SubCommand`]: ./struct.SubCommand.html
     pub fn author<S: Into<&'b str>>(mut self, author: S) -> Self {
         self.p.meta.author = Some(author.into());
         self
@@ -282,6 +338,9 @@ impl<'a, 'b> App<'a, 'b> {
     /// information is displayed **after** the auto-generated help information. This is often used
     /// to describe how to use the arguments, or caveats to be noted.
     ///
+    /// When [`AppSettings::ColoredHelp`] is set, any line starting with `$` is treated as an
+    /// example command and highlighted in the "good" color.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -290,6 +349,15 @@ impl<'a, 'b> App<'a, 'b> {
     ///     .after_help("Does really amazing things to great people...but be careful with -R")
     /// # ;
     /// ```
+    ///
+    /// ```no_run
+    /// # use clap::{App, AppSettings};
+    /// App::new("myprog")
+    ///     .setting(AppSettings::ColoredHelp)
+    ///     .after_help("EXAMPLES:\n    $ myprog -R world")
+    /// # ;
+    /// ```
+    /// [`AppSettings::ColoredHelp`]: ./enum.AppSettings.html#variant.ColoredHelp
     pub fn after_help<S: Into<&'b str>>(mut self, help: S) -> Self {
         self.p.meta.more_help = Some(help.into());
         self
@@ -318,6 +386,8 @@ impl<'a, 'b> App<'a, 'b> {
     /// **NOTE:** If only `version` is provided, and not [`App::long_version`] but the user
     /// requests `--version` clap will still display the contents of `version` appropriately
     ///
+    /// Any [`SubCommand`]s which don't set their own version inherit this one.
+    ///
     /// **Pro-tip:** Use `clap`s convenience macro [`crate_version!`] to automatically set your
     /// application's version to the same thing as your crate at compile time. See the [`examples/`]
     /// directory for more information
@@ -333,6 +403,7 @@ impl<'a, 'b> App<'a, 'b> {
     /// [`crate_version!`]: ./macro.crate_version!.html
     /// [`examples/`]: https://github.com/clap-rs/clap/tree/master/examples
     /// [`App::long_version`]: ./struct.App.html#method.long_version
+    /// [`SubCommand`]: ./struct.SubCommand.html
     pub fn version<S: Into<&'b str>>(mut self, ver: S) -> Self {
         self.p.meta.version = Some(ver.into());
         self
@@ -398,6 +469,33 @@ impl<'a, 'b> App<'a, 'b> {
         self
     }
 
+    /// Adds an alternate usage line, for apps with several distinct invocation forms. Each call
+    /// appends another line, and all of them are displayed under the `USAGE:` heading in place
+    /// of the auto-generated usage string.
+    ///
+    /// **CAUTION:** Like [`App::usage`], this disables `clap`s "context-aware" usage strings.
+    /// [`App::usage`] takes precedence if both are used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let mut app = App::new("tar")
+    ///     .usage_line("tar -c [-f ARCHIVE] [FILE]...")
+    ///     .usage_line("tar -x [-f ARCHIVE] [MEMBER]...");
+    /// let mut out = vec![];
+    /// app.write_usage(&mut out).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(out).unwrap(),
+    ///     "USAGE:\n    tar -c [-f ARCHIVE] [FILE]...\n    tar -x [-f ARCHIVE] [MEMBER]...\n"
+    /// );
+    /// ```
+    /// [`App::usage`]: ./struct.App.html#method.usage
+    pub fn usage_line<S: Into<&'b str>>(mut self, usage: S) -> Self {
+        self.p.meta.usage_lines.push(usage.into());
+        self
+    }
+
     /// Sets a custom help message and overrides the auto-generated one. This should only be used
     /// when the auto-generated message does not suffice.
     ///
@@ -571,6 +669,236 @@ impl<'a, 'b> App<'a, 'b> {
         self
     }
 
+    /// Sets the heading of the `FLAGS:` section in the help message. The trailing `:` is added
+    /// automatically.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let help = App::new("myprog")
+    ///     .flags_heading("SWITCHES")
+    ///     .arg(Arg::with_name("debug").short("d"))
+    ///     .gen_help();
+    /// assert!(help.contains("SWITCHES:\n"));
+    /// ```
+    pub fn flags_heading<S: Into<&'b str>>(mut self, heading: S) -> Self {
+        self.p.meta.flags_heading = Some(heading.into());
+        self
+    }
+
+    /// Sets the heading of the `OPTIONS:` section in the help message, which is also used for the
+    /// combined section of [`AppSettings::UnifiedHelpMessage`]. The trailing `:` is added
+    /// automatically.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let help = App::new("myprog")
+    ///     .options_heading("SETTINGS")
+    ///     .arg(Arg::with_name("config").long("config").takes_value(true))
+    ///     .gen_help();
+    /// assert!(help.contains("SETTINGS:\n"));
+    /// ```
+    /// [`AppSettings::UnifiedHelpMessage`]: ./enum.AppSettings.html#variant.UnifiedHelpMessage
+    pub fn options_heading<S: Into<&'b str>>(mut self, heading: S) -> Self {
+        self.p.meta.opts_heading = Some(heading.into());
+        self
+    }
+
+    /// Sets the heading of the `ARGS:` section (i.e. positional arguments) in the help message.
+    /// The trailing `:` is added automatically.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let help = App::new("myprog")
+    ///     .positionals_heading("ARGUMENTS")
+    ///     .arg(Arg::with_name("input"))
+    ///     .gen_help();
+    /// assert!(help.contains("ARGUMENTS:\n"));
+    /// ```
+    pub fn positionals_heading<S: Into<&'b str>>(mut self, heading: S) -> Self {
+        self.p.meta.pos_heading = Some(heading.into());
+        self
+    }
+
+    /// Routes all help, version and error text through an [`OutputSink`] instead of writing it
+    /// to `stdout`/`stderr`. The sink is inherited by all child [`SubCommand`]s which don't have
+    /// their own.
+    ///
+    /// **NOTE:** The version message is written to the sink as soon as it's requested, even by
+    /// the `*_safe` methods. Errors (including help messages) returned by those methods can be
+    /// written to a sink with [`Error::write_to_sink`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, OutputKind, OutputSink};
+    /// struct Chat;
+    ///
+    /// impl OutputSink for Chat {
+    ///     fn write_line(&self, text: &str, kind: OutputKind) {
+    ///         println!("[{:?}] {}", kind, text);
+    ///     }
+    /// }
+    ///
+    /// App::new("myprog")
+    ///     .output_sink(Chat)
+    /// # ;
+    /// ```
+    /// [`OutputSink`]: ./trait.OutputSink.html
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [`Error::write_to_sink`]: ./struct.Error.html#method.write_to_sink
+    pub fn output_sink<S: OutputSink + 'static>(mut self, sink: S) -> Self {
+        self.p.meta.sink = Some(Rc::new(sink));
+        self
+    }
+
+    /// Sets up `clap` for its usual home, the ClassiCube chat: all help, version and error text
+    /// is routed through `sink`, and the help and errors headed there are colored with
+    /// ClassiCube color codes (the chat isn't a terminal, but it does understand colors). Help
+    /// rendered directly, such as with [`App::print_help`] or [`App::gen_help`], isn't headed for
+    /// the chat and so stays plain, unless [`AppSettings::ColorAlways`] is also set.
+    ///
+    /// This is a shorthand for [`App::output_sink`] plus [`AppSettings::ColoredHelp`], and like
+    /// both of them also applies to all child [`SubCommand`]s.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, OutputKind, OutputSink};
+    /// struct Chat;
+    ///
+    /// impl OutputSink for Chat {
+    ///     fn write_line(&self, text: &str, _kind: OutputKind) {
+    ///         println!("{}", text);
+    ///     }
+    /// }
+    ///
+    /// let app = App::new("myprog")
+    ///     .about("does things")
+    ///     .color_auto_for_chat(Chat);
+    ///
+    /// let err = app.clone().get_matches_from_safe(vec!["myprog", "--help"]).unwrap_err();
+    /// assert!(err.message.contains("&e"));
+    /// assert!(!app.gen_help().contains("&e"));
+    /// ```
+    /// [`App::output_sink`]: ./struct.App.html#method.output_sink
+    /// [`App::print_help`]: ./struct.App.html#method.print_help
+    /// [`App::gen_help`]: ./struct.App.html#method.gen_help
+    /// [`AppSettings::ColorAlways`]: ./enum.AppSettings.html#variant.ColorAlways
+    /// [`AppSettings::ColoredHelp`]: ./enum.AppSettings.html#variant.ColoredHelp
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    pub fn color_auto_for_chat<S: OutputSink + 'static>(self, sink: S) -> Self {
+        let mut app = self.output_sink(sink).global_setting(AppSettings::ColoredHelp);
+        app.p.meta.chat_color = true;
+        app
+    }
+
+    /// Sets the colors used to highlight help messages and errors, for when the defaults don't
+    /// suit the surrounding output. The palette is inherited by all child [`SubCommand`]s which
+    /// don't have their own. Colors are still only used when coloring is enabled, such as with
+    /// [`AppSettings::ColoredHelp`] or [`AppSettings::ColorAlways`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, AppSettings, Color, Palette};
+    /// App::new("myprog")
+    ///     .setting(AppSettings::ColoredHelp)
+    ///     .palette(Palette {
+    ///         good: Color::Cyan,
+    ///         error: Color::Magenta,
+    ///         ..Palette::default()
+    ///     })
+    /// # ;
+    /// ```
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [`AppSettings::ColoredHelp`]: ./enum.AppSettings.html#variant.ColoredHelp
+    /// [`AppSettings::ColorAlways`]: ./enum.AppSettings.html#variant.ColorAlways
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.p.meta.palette = Some(palette);
+        self
+    }
+
+    /// Bounds how much input will be parsed, which protects against maliciously large input when
+    /// the arguments come from an untrusted source such as the ClassiCube chat. The limits are
+    /// inherited by all child [`SubCommand`]s which don't have their own. See [`ParseLimits`] for
+    /// the available limits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind, ParseLimits};
+    /// let res = App::new("myprog")
+    ///     .parse_limits(ParseLimits::new().max_tokens(2))
+    ///     .arg(Arg::with_name("input").multiple(true))
+    ///     .get_matches_from_safe(vec!["myprog", "a", "b", "c"]);
+    ///
+    /// assert!(res.is_err());
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::LimitExceeded);
+    /// ```
+    /// [`ParseLimits`]: ./struct.ParseLimits.html
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    pub fn parse_limits(mut self, limits: ParseLimits) -> Self {
+        self.p.meta.limits = limits;
+        self
+    }
+
+    /// Replaces any argument which is exactly `name` with the arguments in `target` before
+    /// parsing, such as to provide a shorthand for a longer invocation. Replacements apply to the
+    /// whole command line, including the arguments of any [`SubCommand`], but never to arguments
+    /// after a `--`.
+    ///
+    /// **NOTE:** Replacement happens once, after any [response files] have been expanded, so the
+    /// arguments of `target` are used as is: they are neither replaced again nor expanded if they
+    /// look like `@path`. The [`ParseLimits`] apply to the arguments resulting from both steps,
+    /// and are checked as the replacements are made.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myprog")
+    ///     .replace("--spawn", &["--world", "main", "--pos", "0,64,0"])
+    ///     .arg(Arg::with_name("world").long("world").takes_value(true))
+    ///     .arg(Arg::with_name("pos").long("pos").takes_value(true))
+    ///     .get_matches_from(vec!["myprog", "--spawn"]);
+    ///
+    /// assert_eq!(m.value_of("world"), Some("main"));
+    /// assert_eq!(m.value_of("pos"), Some("0,64,0"));
+    /// ```
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [response files]: ./enum.AppSettings.html#variant.AllowResponseFiles
+    /// [`ParseLimits`]: ./struct.ParseLimits.html
+    pub fn replace(mut self, name: &'b str, target: &[&'b str]) -> Self {
+        self.p.meta.replacers.push((name, target.to_vec()));
+        self
+    }
+
+    /// Sets the status [`App::get_matches_from`] exits the process with when parsing fails, for
+    /// tools following their own conventions (many GNU tools use `2` for usage errors). Displaying
+    /// the help or version message still exits with `0`. Defaults to `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myprog")
+    ///     .error_exit_code(2)
+    ///     .arg(Arg::with_name("input").required(true))
+    ///     .get_matches_from(vec!["myprog"]);
+    /// // the process has exited with status 2
+    /// ```
+    /// [`App::get_matches_from`]: ./struct.App.html#method.get_matches_from
+    pub fn error_exit_code(mut self, code: i32) -> Self {
+        self.p.meta.error_exit_code = Some(code);
+        self
+    }
+
     /// Enables a single command, or [`SubCommand`], level settings.
     ///
     /// See [`AppSettings`] for a full list of possibilities and examples.
@@ -660,6 +988,143 @@ impl<'a, 'b> App<'a, 'b> {
         self
     }
 
+    /// Stops `clap` from generating *any* of its automatic help and version handling, for
+    /// applications which manage their own: no `-h`/`--help` or `-V`/`--version` flags and no
+    /// `help` subcommand are added, here or in any child [`SubCommand`]. Arguments named `help`,
+    /// `version`, `h` or `V` can then be declared without colliding with the built in ones, and
+    /// behave like any other argument.
+    ///
+    /// This is a shorthand for the global [`AppSettings::DisableHelpFlags`],
+    /// [`AppSettings::DisableVersion`] and [`AppSettings::DisableHelpSubcommand`] settings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, SubCommand};
+    /// let m = App::new("myprog")
+    ///     .no_auto_help_version()
+    ///     .arg(Arg::with_name("help").short("h").long("help"))
+    ///     .subcommand(SubCommand::with_name("sub"))
+    ///     .get_matches_from(vec!["myprog", "--help"]);
+    ///
+    /// assert!(m.is_present("help"));
+    /// ```
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [`AppSettings::DisableHelpFlags`]: ./enum.AppSettings.html#variant.DisableHelpFlags
+    /// [`AppSettings::DisableVersion`]: ./enum.AppSettings.html#variant.DisableVersion
+    /// [`AppSettings::DisableHelpSubcommand`]: ./enum.AppSettings.html#variant.DisableHelpSubcommand
+    pub fn no_auto_help_version(self) -> Self {
+        self.global_settings(&[
+            AppSettings::DisableHelpFlags,
+            AppSettings::DisableVersion,
+            AppSettings::DisableHelpSubcommand,
+        ])
+    }
+
+    /// Hides this [`SubCommand`] from the `SUBCOMMANDS:` section of its parent's help message,
+    /// while still allowing it to be used. This is a shorthand for [`AppSettings::Hidden`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, SubCommand};
+    /// let m = App::new("myprog")
+    ///     .subcommand(SubCommand::with_name("debug")
+    ///         .hidden(true))
+    ///     .get_matches_from(vec!["myprog", "debug"]);
+    ///
+    /// assert_eq!(m.subcommand_name(), Some("debug"));
+    /// ```
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [`AppSettings::Hidden`]: ./enum.AppSettings.html#variant.Hidden
+    pub fn hidden(mut self, h: bool) -> Self {
+        if h {
+            self.p.set(AppSettings::Hidden);
+        } else {
+            self.p.unset(AppSettings::Hidden);
+        }
+        self
+    }
+
+    /// Stops `clap` from generating the automatic `-h`/`--help` flags for this command, so that
+    /// `-h` can be used for something else entirely. Unlike [`App::no_auto_help_version`] this
+    /// only affects this command, and leaves the version flags and the `help` subcommand alone.
+    /// This is a shorthand for [`AppSettings::DisableHelpFlags`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myprog")
+    ///     .disable_help_flag(true)
+    ///     .arg(Arg::with_name("host").short("h").takes_value(true))
+    ///     .get_matches_from(vec!["myprog", "-h", "localhost"]);
+    ///
+    /// assert_eq!(m.value_of("host"), Some("localhost"));
+    /// ```
+    /// [`App::no_auto_help_version`]: ./struct.App.html#method.no_auto_help_version
+    /// [`AppSettings::DisableHelpFlags`]: ./enum.AppSettings.html#variant.DisableHelpFlags
+    pub fn disable_help_flag(mut self, d: bool) -> Self {
+        if d {
+            self.p.set(AppSettings::DisableHelpFlags);
+        } else {
+            self.p.unset(AppSettings::DisableHelpFlags);
+        }
+        self
+    }
+
+    /// Stops `clap` from generating the automatic `-V`/`--version` flags for this command. This
+    /// is a shorthand for [`AppSettings::DisableVersion`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myprog")
+    ///     .version("v1.1")
+    ///     .disable_version_flag(true)
+    ///     .arg(Arg::with_name("verbose").short("V"))
+    ///     .get_matches_from(vec!["myprog", "-V"]);
+    ///
+    /// assert!(m.is_present("verbose"));
+    /// ```
+    /// [`AppSettings::DisableVersion`]: ./enum.AppSettings.html#variant.DisableVersion
+    pub fn disable_version_flag(mut self, d: bool) -> Self {
+        if d {
+            self.p.set(AppSettings::DisableVersion);
+        } else {
+            self.p.unset(AppSettings::DisableVersion);
+        }
+        self
+    }
+
+    /// Stops `clap` from generating the automatic `help` subcommand for this command, so that
+    /// `help` can be declared as a regular [`SubCommand`]. This is a shorthand for
+    /// [`AppSettings::DisableHelpSubcommand`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, SubCommand};
+    /// let m = App::new("myprog")
+    ///     .disable_help_subcommand(true)
+    ///     .subcommand(SubCommand::with_name("help")
+    ///         .arg(Arg::with_name("topic")))
+    ///     .get_matches_from(vec!["myprog", "help", "rules"]);
+    ///
+    /// assert_eq!(m.subcommand_matches("help").unwrap().value_of("topic"), Some("rules"));
+    /// ```
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [`AppSettings::DisableHelpSubcommand`]: ./enum.AppSettings.html#variant.DisableHelpSubcommand
+    pub fn disable_help_subcommand(mut self, d: bool) -> Self {
+        if d {
+            self.p.set(AppSettings::DisableHelpSubcommand);
+        } else {
+            self.p.unset(AppSettings::DisableHelpSubcommand);
+        }
+        self
+    }
+
     /// Disables a single command, or [`SubCommand`], level setting.
     ///
     /// See [`AppSettings`] for a full list of possibilities and examples.
@@ -759,6 +1224,31 @@ impl<'a, 'b> App<'a, 'b> {
         self
     }
 
+    /// Sets the number of spaces used to indent the usage and each argument in the help message.
+    /// Help text moved to its own line is indented by three times this width. Defaults to `4`.
+    ///
+    /// Any [`SubCommand`]s which don't set their own indent use this one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let help = App::new("myprog")
+    ///     .help_indent(2)
+    ///     .arg(Arg::with_name("world")
+    ///         .long("world")
+    ///         .help("world to join"))
+    ///     .gen_help();
+    ///
+    /// assert!(help.contains("\n  -h, --help"));
+    /// assert!(help.contains("\n      --world      world to join"));
+    /// ```
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    pub fn help_indent(mut self, width: usize) -> Self {
+        self.p.meta.help_indent = Some(width);
+        self
+    }
+
     /// Adds an [argument] to the list of valid possibilities.
     ///
     /// # Examples
@@ -829,11 +1319,17 @@ impl<'a, 'b> App<'a, 'b> {
     }
 
     /// Adds multiple [arguments] at once from a usage string, one per line. See
-    /// [`Arg::from_usage`] for details on the syntax and rules supported.
+    /// [`Arg::from_usage`] for details on the syntax and rules supported. Surrounding whitespace
+    /// is trimmed from each line, and blank lines are ignored.
     ///
     /// **NOTE:** Like [`App::arg_from_usage`] the downside is you only set properties for the
     /// [`Arg`]s which [`Arg::from_usage`] supports.
     ///
+    /// # Panics
+    ///
+    /// If a line doesn't name an argument, e.g. because it only holds a help string. The panic
+    /// message includes the offending line and its line number.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -851,12 +1347,19 @@ impl<'a, 'b> App<'a, 'b> {
     /// [`App::arg_from_usage`]: ./struct.App.html#method.arg_from_usage
     /// [`Arg`]: ./struct.Arg.html
     pub fn args_from_usage(mut self, usage: &'a str) -> Self {
-        for line in usage.lines() {
+        for (i, line) in usage.lines().enumerate() {
             let l = line.trim();
             if l.is_empty() {
                 continue;
             }
-            self.p.add_arg(Arg::from_usage(l));
+            let arg = UsageParser::from_usage(l).parse();
+            assert!(
+                !arg.b.name.is_empty(),
+                "No name found for Arg when parsing line {} of usage string: {}",
+                i + 1,
+                l
+            );
+            self.p.add_arg(arg);
         }
         self
     }
@@ -1086,6 +1589,74 @@ impl<'a, 'b> App<'a, 'b> {
         self
     }
 
+    /// Registers a handler to be called with the [`ArgMatches`] of the [`SubCommand`] `name`
+    /// whenever it's used. Handlers are only invoked by [`App::run_from`] and
+    /// [`App::run_from_safe`], and at most one handler (the one for the subcommand actually used)
+    /// runs per parse. If no subcommand was used, no handler runs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, SubCommand};
+    /// App::new("myprog")
+    ///     .subcommand(SubCommand::with_name("kick")
+    ///         .arg(Arg::with_name("player").required(true)))
+    ///     .subcommand_handler("kick", |m| {
+    ///         println!("Kicking {}", m.value_of("player").unwrap());
+    ///     })
+    ///     .run_from(vec!["myprog", "kick", "Notch"]);
+    /// ```
+    /// [`ArgMatches`]: ./struct.ArgMatches.html
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [`App::run_from`]: ./struct.App.html#method.run_from
+    /// [`App::run_from_safe`]: ./struct.App.html#method.run_from_safe
+    pub fn subcommand_handler<F>(mut self, name: &'b str, handler: F) -> Self
+    where
+        F: Fn(&ArgMatches) + 'static,
+    {
+        self.p.meta.handlers.push((name, Rc::new(handler)));
+        self
+    }
+
+    /// Adds a validator which is run against the [`ArgMatches`] once parsing (and all of the
+    /// declarative checks such as [`Arg::requires`] and [`Arg::conflicts_with`]) has succeeded.
+    /// This is the escape hatch for rules spanning several arguments which can't be expressed
+    /// any other way.
+    ///
+    /// Validators run in the order they were added, and the first one to return `Err` stops
+    /// parsing with an [`ErrorKind::ValueValidation`] error containing its message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let res = App::new("myprog")
+    ///     .arg(Arg::with_name("mode").long("mode").takes_value(true))
+    ///     .arg(Arg::with_name("speed").long("speed").takes_value(true))
+    ///     .validate_with(|m| {
+    ///         if m.value_of("mode") == Some("walk") && m.value_of("speed") == Some("fast") {
+    ///             Err("walking can't be fast".to_owned())
+    ///         } else {
+    ///             Ok(())
+    ///         }
+    ///     })
+    ///     .get_matches_from_safe(vec!["myprog", "--mode", "walk", "--speed", "fast"]);
+    ///
+    /// assert!(res.is_err());
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::ValueValidation);
+    /// ```
+    /// [`ArgMatches`]: ./struct.ArgMatches.html
+    /// [`Arg::requires`]: ./struct.Arg.html#method.requires
+    /// [`Arg::conflicts_with`]: ./struct.Arg.html#method.conflicts_with
+    /// [`ErrorKind::ValueValidation`]: ./enum.ErrorKind.html#variant.ValueValidation
+    pub fn validate_with<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&ArgMatches) -> StdResult<(), String> + 'static,
+    {
+        self.p.meta.validators.push(Rc::new(f));
+        self
+    }
+
     /// Allows custom ordering of [`SubCommand`]s within the help message. Subcommands with a lower
     /// value will be displayed first in the help message. This is helpful when one would like to
     /// emphasise frequently used subcommands, or prioritize those towards the top of the list.
@@ -1226,6 +1797,36 @@ impl<'a, 'b> App<'a, 'b> {
         Help::write_app_help(w, self, false)
     }
 
+    /// Renders the full help message into a `String`, exactly as [`App::print_help`] would print
+    /// it (i.e. as if the user ran `-h`)
+    ///
+    /// Unlike [`App::write_help`] this includes propagated global arguments and the
+    /// autogenerated help/version arguments. The `App` itself is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::App;
+    /// let app = App::new("myprog")
+    ///     .version("1.0")
+    ///     .about("does things");
+    /// let help = app.gen_help();
+    /// assert!(help.starts_with("myprog 1.0\ndoes things\n"));
+    /// ```
+    /// [`App::print_help`]: ./struct.App.html#method.print_help
+    /// [`App::write_help`]: ./struct.App.html#method.write_help
+    pub fn gen_help(&self) -> String {
+        let mut app = self.clone();
+        app.p.propagate_globals();
+        app.p.propagate_settings();
+        app.p.derive_display_order();
+        app.p.create_help_and_version();
+
+        let mut buf = vec![];
+        Help::write_app_help(&mut buf, &app, false).expect(INTERNAL_ERROR_MSG);
+        String::from_utf8(buf).expect(INVALID_UTF8)
+    }
+
     /// Writes the full help message to the user to a [`io::Write`] object in the same method as if
     /// the user ran `--help`
     ///
@@ -1255,6 +1856,46 @@ impl<'a, 'b> App<'a, 'b> {
         Help::write_app_help(w, self, true)
     }
 
+    /// Prints the usage message to [`io::stdout()`] using a [`BufWriter`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::App;
+    /// let mut app = App::new("myprog");
+    /// app.print_usage();
+    /// ```
+    /// [`io::stdout()`]: https://doc.rust-lang.org/std/io/fn.stdout.html
+    /// [`BufWriter`]: https://doc.rust-lang.org/std/io/struct.BufWriter.html
+    pub fn print_usage(&mut self) -> ClapResult<()> {
+        let out = io::stdout();
+        let mut buf_w = BufWriter::new(out.lock());
+        self.write_usage(&mut buf_w)
+    }
+
+    /// Writes the usage message (i.e. the `USAGE:` heading followed by the usage string) to a
+    /// [`io::Write`] object, exactly as it appears in the help message
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::App;
+    /// let mut app = App::new("myprog");
+    /// let mut out = vec![];
+    /// app.write_usage(&mut out).expect("failed to write usage");
+    /// assert_eq!(String::from_utf8(out).unwrap(), "USAGE:\n    myprog\n");
+    /// ```
+    /// [`io::Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+    pub fn write_usage<W: Write>(&mut self, w: &mut W) -> ClapResult<()> {
+        self.p.propagate_globals();
+        self.p.propagate_settings();
+        self.p.derive_display_order();
+        self.p.create_help_and_version();
+
+        try!(writeln!(w, "{}", usage::create_usage_with_title(&self.p, &[])));
+        w.flush().map_err(From::from)
+    }
+
     /// Writes the version message to the user to a [`io::Write`] object as if the user ran `-V`.
     ///
     /// **NOTE:** clap has the ability to distinguish between "short" and "long" version messages
@@ -1433,6 +2074,145 @@ impl<'a, 'b> App<'a, 'b> {
         self.p.gen_completions_to(for_shell, buf);
     }
 
+    /// Writes a Bash completion script for this `App` to `buf`. The script registers a
+    /// `complete -F _{bin_name}` function that offers flags, options, subcommand names and the
+    /// [`Arg::possible_values`] of options, following any subcommands already on the command line.
+    ///
+    /// Unlike [`App::gen_completions_to`] this leaves `self` untouched, so it can be called on an
+    /// `App` that is still going to be parsed with.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, SubCommand};
+    /// # use std::io;
+    /// let app = App::new("myapp")
+    ///     .subcommand(SubCommand::with_name("test"));
+    /// app.gen_completions_bash("myapp", &mut io::stdout());
+    /// ```
+    /// [`Arg::possible_values`]: ./struct.Arg.html#method.possible_values
+    /// [`App::gen_completions_to`]: ./struct.App.html#method.gen_completions_to
+    pub fn gen_completions_bash<W: Write>(&self, bin_name: &str, buf: &mut W) {
+        self.clone().gen_completions_to(bin_name, Shell::Bash, buf);
+    }
+
+    /// Writes a ZSH `#compdef` completion script for this `App` to `buf`. Every argument is
+    /// passed to `_arguments` with its help text as the description, required positional
+    /// arguments are marked as such, and subcommands are listed with `_describe` together with
+    /// their [`App::about`] text.
+    ///
+    /// Like [`App::gen_completions_bash`] this leaves `self` untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, SubCommand};
+    /// # use std::io;
+    /// let app = App::new("myapp")
+    ///     .subcommand(SubCommand::with_name("test").about("tests things"));
+    /// app.gen_completions_zsh("myapp", &mut io::stdout());
+    /// ```
+    /// [`App::about`]: ./struct.App.html#method.about
+    /// [`App::gen_completions_bash`]: ./struct.App.html#method.gen_completions_bash
+    pub fn gen_completions_zsh<W: Write>(&self, bin_name: &str, buf: &mut W) {
+        self.clone().gen_completions_to(bin_name, Shell::Zsh, buf);
+    }
+
+    /// Writes a Fish completion script for this `App` to `buf`, made of one `complete -c
+    /// {bin_name}` line per flag, option and subcommand. Descriptions are taken from each
+    /// argument's help text and each subcommand's [`App::about`], options are marked as taking a
+    /// parameter (`-r`), and [`Arg::possible_values`] are offered as candidates.
+    ///
+    /// Like [`App::gen_completions_bash`] this leaves `self` untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, SubCommand};
+    /// # use std::io;
+    /// let app = App::new("myapp")
+    ///     .subcommand(SubCommand::with_name("test").about("tests things"));
+    /// app.gen_completions_fish("myapp", &mut io::stdout());
+    /// ```
+    /// [`App::about`]: ./struct.App.html#method.about
+    /// [`Arg::possible_values`]: ./struct.Arg.html#method.possible_values
+    /// [`App::gen_completions_bash`]: ./struct.App.html#method.gen_completions_bash
+    pub fn gen_completions_fish<W: Write>(&self, bin_name: &str, buf: &mut W) {
+        self.clone().gen_completions_to(bin_name, Shell::Fish, buf);
+    }
+
+    /// Generate completion files for several shells at once, writing each one into `dir` under
+    /// the name that shell expects (`{bin_name}.bash`, `{bin_name}.fish`, `_{bin_name}` for ZSH,
+    /// `_{bin_name}.ps1` and `{bin_name}.elv`). `dir`, and any missing parents, will be created.
+    ///
+    /// If no [`App::bin_name`] has been set, the name of the `App` is used.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, Shell};
+    /// # use std::path::Path;
+    /// let mut app = App::new("myapp");
+    /// app.gen_completions_to_dir(Path::new("target/completions"), &[Shell::Bash, Shell::Zsh])
+    ///     .expect("failed to write completions");
+    /// // target/completions/myapp.bash and target/completions/_myapp now exist
+    /// ```
+    /// [`App::bin_name`]: ./struct.App.html#method.bin_name
+    pub fn gen_completions_to_dir(&mut self, dir: &Path, shells: &[Shell]) -> io::Result<()> {
+        if self.p.meta.bin_name.is_none() {
+            self.p.meta.bin_name = Some(self.p.meta.name.clone());
+        }
+        self.p.gen_completions_to_dir(dir, shells)
+    }
+
+    /// Writes a man page for this `App` to `buf`. The page contains a `NAME` section built from
+    /// the name and [`App::about`], a `SYNOPSIS` holding the same usage string shown by
+    /// [`App::print_usage`], and `OPTIONS`, `ARGS` and `SUBCOMMANDS` sections listing every
+    /// argument and subcommand that isn't hidden together with its help text.
+    ///
+    /// If no [`App::bin_name`] has been set, the name of the `App` is used. `self` is left
+    /// untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # use std::io;
+    /// let app = App::new("myapp")
+    ///     .about("Does great things");
+    /// app.gen_manpage(&mut io::stdout()).expect("failed to write man page");
+    /// ```
+    /// [`App::about`]: ./struct.App.html#method.about
+    /// [`App::print_usage`]: ./struct.App.html#method.print_usage
+    /// [`App::bin_name`]: ./struct.App.html#method.bin_name
+    pub fn gen_manpage<W: Write>(&self, buf: &mut W) -> io::Result<()> {
+        let mut app = self.clone();
+        if app.p.meta.bin_name.is_none() {
+            app.p.meta.bin_name = Some(app.p.meta.name.clone());
+        }
+        app.p.gen_manpage(buf)
+    }
+
+    /// Generate a man page for this `App` and write it to `path`, which by convention is named
+    /// `{bin_name}.1`. Any missing parent directories of `path` will be created.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// # use std::path::Path;
+    /// let mut app = App::new("myapp")
+    ///     .about("Does great things");
+    /// app.gen_manpage_to(Path::new("target/man/myapp.1"))
+    ///     .expect("failed to write man page");
+    /// ```
+    pub fn gen_manpage_to(&mut self, path: &Path) -> io::Result<()> {
+        if self.p.meta.bin_name.is_none() {
+            self.p.meta.bin_name = Some(self.p.meta.name.clone());
+        }
+        self.p.gen_manpage_to(path)
+    }
+
     /// Starts the parsing process, upon a failed parse an error will be displayed to the user and
     /// the process will exit with the appropriate error code. By default this method gets all user
     /// provided arguments from [`env::args_os`] in order to allow for invalid UTF-8 code points,
@@ -1506,6 +2286,20 @@ impl<'a, 'b> App<'a, 'b> {
         T: Into<OsString> + Clone,
     {
         self.get_matches_from_safe_borrow(itr).unwrap_or_else(|e| {
+            let code = self.p.meta.error_exit_code.unwrap_or(1);
+            if let Some(sink) = self.p.meta.sink.clone() {
+                e.write_to_sink(&*sink);
+                if e.use_stderr() && self.p.is_set(AppSettings::WaitOnError) {
+                    sink.write_line("", OutputKind::Warning);
+                    sink.write_line("Press [ENTER] / [RETURN] to continue...", OutputKind::Warning);
+                    let mut s = String::new();
+                    let i = io::stdin();
+                    i.lock().read_line(&mut s).unwrap();
+                }
+                drop(self);
+                process::exit(if e.use_stderr() { code } else { 0 });
+            }
+
             // Otherwise, write to stderr and exit
             if e.use_stderr() {
                 wlnerr!("{}", e.message);
@@ -1517,7 +2311,7 @@ impl<'a, 'b> App<'a, 'b> {
                 }
                 drop(self);
                 drop(e);
-                process::exit(1);
+                process::exit(code);
             }
 
             drop(self);
@@ -1572,6 +2366,11 @@ impl<'a, 'b> App<'a, 'b> {
     /// **NOTE:** The first argument will be parsed as the binary name unless
     /// [`AppSettings::NoBinaryName`] is used
     ///
+    /// **NOTE:** Before parsing, the arguments are rewritten in a fixed order. Any [response
+    /// files] are expanded first, then the [`App::replace`] replacements are applied to the
+    /// result. The [`ParseLimits`] apply to the final arguments, but are checked as each step
+    /// adds arguments, so oversized input is rejected before it's all been read.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -1586,7 +2385,84 @@ impl<'a, 'b> App<'a, 'b> {
     /// [`App`]: ./struct.App.html
     /// [`App::get_matches_from_safe`]: ./struct.App.html#method.get_matches_from_safe
     /// [`AppSettings::NoBinaryName`]: ./enum.AppSettings.html#variant.NoBinaryName
+    /// [response files]: ./enum.AppSettings.html#variant.AllowResponseFiles
+    /// [`App::replace`]: ./struct.App.html#method.replace
+    /// [`ParseLimits`]: ./struct.ParseLimits.html
     pub fn get_matches_from_safe_borrow<I, T>(&mut self, itr: I) -> ClapResult<ArgMatches<'a>>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let mut matcher = ArgMatcher::new();
+        try!(self.get_matches_with(itr, &mut matcher));
+        Ok(matcher.into())
+    }
+
+    /// Parses a whole command line given as a single string, returning the usual parse result
+    /// along with the role each token played, as `(start, end, kind)` byte offsets into `line`.
+    /// This allows a user interface to highlight a command as it's being typed.
+    ///
+    /// The line is split into tokens on whitespace, with no support for quoting. Tokens are
+    /// annotated in order up to the point where parsing stopped, so when there is an error the
+    /// token which caused it (and any following it) won't have a span. Neither the binary name nor
+    /// a `--` separating trailing values is annotated.
+    ///
+    /// **NOTE:** The tokens are parsed exactly as they appear in `line`, so [response files] aren't
+    /// expanded and [`App::replace`] replacements aren't applied.
+    ///
+    /// **NOTE:** The first token will be parsed as the binary name unless
+    /// [`AppSettings::NoBinaryName`] is used
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, SubCommand, TokenKind};
+    /// let mut app = App::new("myprog")
+    ///     .arg(Arg::with_name("verbose").short("v"))
+    ///     .subcommand(SubCommand::with_name("tp")
+    ///         .arg(Arg::with_name("player")));
+    ///
+    /// let (res, spans) = app.get_matches_with_spans("myprog -v tp Notch");
+    /// assert!(res.is_ok());
+    /// assert_eq!(spans, vec![
+    ///     (7, 9, TokenKind::Flag),
+    ///     (10, 12, TokenKind::Subcommand),
+    ///     (13, 18, TokenKind::Positional),
+    /// ]);
+    /// ```
+    /// [`AppSettings::NoBinaryName`]: ./enum.AppSettings.html#variant.NoBinaryName
+    /// [response files]: ./enum.AppSettings.html#variant.AllowResponseFiles
+    /// [`App::replace`]: ./struct.App.html#method.replace
+    pub fn get_matches_with_spans(
+        &mut self,
+        line: &str,
+    ) -> (ClapResult<ArgMatches<'a>>, Vec<(usize, usize, TokenKind)>) {
+        let mut tokens = vec![];
+        let mut start = None;
+        for (i, c) in line.char_indices().chain(iter::once((line.len(), ' '))) {
+            match (start, c.is_whitespace()) {
+                (None, false) => start = Some(i),
+                (Some(s), true) => {
+                    tokens.push((s, i));
+                    start = None;
+                }
+                _ => (),
+            }
+        }
+
+        let mut matcher = ArgMatcher::with_tokens();
+        let res = self.get_matches_with(tokens.iter().map(|&(s, e)| &line[s..e]), &mut matcher);
+        let skip = if self.p.is_set(AppSettings::NoBinaryName) { 0 } else { 1 };
+        let spans = tokens
+            .into_iter()
+            .skip(skip)
+            .zip(matcher.tokens().iter())
+            .filter_map(|((s, e), kind)| kind.map(|k| (s, e, k)))
+            .collect();
+        (res.map(|_| matcher.into()), spans)
+    }
+
+    fn get_matches_with<I, T>(&mut self, itr: I, matcher: &mut ArgMatcher<'a>) -> ClapResult<()>
     where
         I: IntoIterator<Item = T>,
         T: Into<OsString> + Clone,
@@ -1600,8 +2476,6 @@ impl<'a, 'b> App<'a, 'b> {
             self.p.set(AppSettings::Propagated);
         }
 
-        let mut matcher = ArgMatcher::new();
-
         let mut it = itr.into_iter();
         // Get the name of the program (argument 1 of env::args()) and determine the
         // actual file
@@ -1614,7 +2488,11 @@ impl<'a, 'b> App<'a, 'b> {
             if let Some(name) = it.next() {
                 let bn_os = name.into();
                 let p = Path::new(&*bn_os);
-                if let Some(f) = p.file_name() {
+                // Windows binaries are named `myprog.exe`, but are run as just `myprog`
+                let is_exe = p.extension()
+                    .and_then(|e| e.to_str())
+                    .map_or(false, |e| e.eq_ignore_ascii_case("exe"));
+                if let Some(f) = if is_exe { p.file_stem() } else { p.file_name() } {
                     if let Some(s) = f.to_os_string().to_str() {
                         if self.p.meta.bin_name.is_none() {
                             self.p.meta.bin_name = Some(s.to_owned());
@@ -1624,15 +2502,208 @@ impl<'a, 'b> App<'a, 'b> {
             }
         }
 
-        // do the real parsing
-        if let Err(e) = self.p.get_matches_with(&mut matcher, &mut it.peekable()) {
+        // The arguments are rewritten in a fixed order, each step running exactly once over the
+        // output of the one before: response files are expanded first, then the `App::replace`
+        // replacements are applied. The `ParseLimits` are checked as each step adds arguments. Anything else,
+        // such as normalizing long separators, only happens as the parser matches each argument.
+        // When recording spans nothing is rewritten, as the spans must line up with the input.
+        if matcher.records_tokens() {
+            self.parse_args(it, matcher)
+        } else if self.p.is_set(AppSettings::AllowResponseFiles) {
+            let args = try!(response_files::expand(it, &self.p.meta.limits, self.p.color()));
+            self.replace_args(args.into_iter(), matcher)
+        } else {
+            self.replace_args(it, matcher)
+        }
+    }
+
+    fn replace_args<I, T>(&mut self, it: I, matcher: &mut ArgMatcher<'a>) -> ClapResult<()>
+    where
+        I: Iterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        if self.p.meta.replacers.is_empty() {
+            return self.parse_args(it, matcher);
+        }
+        let mut args = Vec::new();
+        let mut trailing = false;
+        for arg in it {
+            let arg = arg.into();
+            let target = self.p
+                .meta
+                .replacers
+                .iter()
+                .find(|&&(name, _)| !trailing && arg == name)
+                .map(|&(_, ref target)| target);
+            if let Some(target) = target {
+                debugln!("App::replace_args: replacing {:?} with {:?}", arg, target);
+                for t in target {
+                    try!(self.p.meta.limits.check(&args, OsStr::new(t), self.p.color()));
+                    args.push(t.into());
+                }
+            } else {
+                trailing = trailing || arg == "--";
+                try!(self.p.meta.limits.check(&args, &arg, self.p.color()));
+                args.push(arg);
+            }
+        }
+        self.parse_args(args.into_iter(), matcher)
+    }
+
+    fn parse_args<I, T>(&mut self, it: I, matcher: &mut ArgMatcher<'a>) -> ClapResult<()>
+    where
+        I: Iterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        // do the real parsing, reading no more than the limits allow when they're set
+        if self.p.meta.limits.limits_input() {
+            let args = try!(self.p.meta.limits.collect(it, self.p.color()));
+            try!(self.p.get_matches_with(matcher, &mut args.into_iter().peekable()));
+        } else if let Err(e) = self.p.get_matches_with(matcher, &mut it.peekable()) {
             return Err(e);
         }
 
         let global_arg_vec: Vec<&str> = (&self).p.global_args.iter().map(|ga| ga.b.name).collect();
         matcher.propagate_globals(&global_arg_vec);
+        self.p.set_arg_kinds(&mut matcher.0);
 
-        Ok(matcher.into())
+        Ok(())
+    }
+
+    /// Parses `itr` directly against the [`SubCommand`] `name` (or one of its aliases), as if
+    /// the user had typed the subcommand's name first. This is useful when the host has already
+    /// routed the command, such as a ClassiCube server dispatching `/build` to the `build`
+    /// subcommand. The returned [`ArgMatches`] are the subcommand's own matches.
+    ///
+    /// Unlike [`App::get_matches_from_safe`], `itr` must *not* start with the binary name. Any
+    /// arguments or settings the parent propagates to its subcommands (such as [global]
+    /// arguments) still apply.
+    ///
+    /// An [`ErrorKind::UnrecognizedSubcommand`] error is returned if `name` isn't a subcommand.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, SubCommand};
+    /// let m = App::new("server")
+    ///     .subcommand(SubCommand::with_name("build")
+    ///         .arg(Arg::with_name("block")
+    ///             .required(true)))
+    ///     .get_matches_for_subcommand("build", vec!["stone"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(m.value_of("block"), Some("stone"));
+    /// ```
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [`ArgMatches`]: ./struct.ArgMatches.html
+    /// [`App::get_matches_from_safe`]: ./struct.App.html#method.get_matches_from_safe
+    /// [global]: ./struct.Arg.html#method.global
+    /// [`ErrorKind::UnrecognizedSubcommand`]: ./enum.ErrorKind.html#variant.UnrecognizedSubcommand
+    pub fn get_matches_for_subcommand<I, T>(
+        mut self,
+        name: &str,
+        itr: I,
+    ) -> ClapResult<ArgMatches<'a>>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        if !self.p.is_set(AppSettings::Propagated) {
+            self.p.propagate_globals();
+            self.p.propagate_settings();
+            self.p.derive_display_order();
+            self.p.set(AppSettings::Propagated);
+        }
+
+        let bin_name = self.p
+            .meta
+            .bin_name
+            .clone()
+            .unwrap_or_else(|| self.p.meta.name.clone());
+        let pos = self.p.subcommands.iter().position(|s| {
+            s.p.meta.name == name
+                || s.p
+                    .meta
+                    .aliases
+                    .as_ref()
+                    .map_or(false, |a| a.iter().any(|&(n, _)| n == name))
+        });
+        let mut sc = match pos {
+            Some(i) => self.p.subcommands.swap_remove(i),
+            None => {
+                return Err(Error::unrecognized_subcommand(
+                    name,
+                    &*bin_name,
+                    self.p.color(),
+                ))
+            }
+        };
+        // The parent already propagated everything down to this subcommand
+        sc.p.set(AppSettings::Propagated);
+        let sc_bin_name = format!("{} {}", bin_name, sc.p.meta.name);
+        sc.p.meta.usage = Some(sc_bin_name.clone());
+        sc.p.meta.bin_name = Some(sc_bin_name.clone());
+
+        let args = iter::once(OsString::from(sc_bin_name)).chain(itr.into_iter().map(Into::into));
+        sc.get_matches_from_safe_borrow(args)
+    }
+
+    /// Starts the parsing process in the same manner as [`App::get_matches_from`], and then calls
+    /// the handler registered with [`App::subcommand_handler`] for the [`SubCommand`] which was
+    /// used (if any). The matches are returned afterwards for any further processing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, SubCommand};
+    /// let m = App::new("myprog")
+    ///     .subcommand(SubCommand::with_name("spawn"))
+    ///     .subcommand_handler("spawn", |_| println!("Teleporting to spawn"))
+    ///     .run_from(vec!["myprog", "spawn"]);
+    ///
+    /// assert_eq!(m.subcommand_name(), Some("spawn"));
+    /// ```
+    /// [`App::get_matches_from`]: ./struct.App.html#method.get_matches_from
+    /// [`App::subcommand_handler`]: ./struct.App.html#method.subcommand_handler
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    pub fn run_from<I, T>(mut self, itr: I) -> ArgMatches<'a>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let handlers = mem::replace(&mut self.p.meta.handlers, Vec::new());
+        let m = self.get_matches_from(itr);
+        run_handler(&handlers, &m);
+        m
+    }
+
+    /// Starts the parsing process in the same manner as [`App::get_matches_from_safe`], and then
+    /// calls the handler registered with [`App::subcommand_handler`] for the [`SubCommand`] which
+    /// was used (if any). No handler runs if parsing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, SubCommand};
+    /// let res = App::new("myprog")
+    ///     .subcommand(SubCommand::with_name("spawn"))
+    ///     .subcommand_handler("spawn", |_| println!("Teleporting to spawn"))
+    ///     .run_from_safe(vec!["myprog", "spawn"]);
+    ///
+    /// assert!(res.is_ok());
+    /// ```
+    /// [`App::get_matches_from_safe`]: ./struct.App.html#method.get_matches_from_safe
+    /// [`App::subcommand_handler`]: ./struct.App.html#method.subcommand_handler
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    pub fn run_from_safe<I, T>(mut self, itr: I) -> ClapResult<ArgMatches<'a>>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let handlers = mem::replace(&mut self.p.meta.handlers, Vec::new());
+        let m = try!(self.get_matches_from_safe(itr));
+        run_handler(&handlers, &m);
+        Ok(m)
     }
 }
 
@@ -1836,3 +2907,11 @@ impl<'n, 'e> AnyArg<'n, 'e> for App<'n, 'e> {
 impl<'n, 'e> fmt::Display for App<'n, 'e> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{}", self.p.meta.name) }
 }
+
+fn run_handler(handlers: &[(&str, Rc<Fn(&ArgMatches)>)], m: &ArgMatches) {
+    if let (name, Some(sub_m)) = m.subcommand() {
+        if let Some(&(_, ref handler)) = handlers.iter().find(|&&(n, _)| n == name) {
+            handler(sub_m);
+        }
+    }
+}
@@ -1,31 +1,35 @@
 // Std
+use std::env;
 use std::ffi::{OsStr, OsString};
 use std::fmt::Display;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{self, BufWriter, Write};
 #[cfg(all(feature = "debug", not(any(target_os = "windows", target_arch = "wasm32"))))]
 use std::os::unix::ffi::OsStrExt;
 #[cfg(all(feature = "debug", any(target_os = "windows", target_arch = "wasm32")))]
 use osstringext::OsStrExt3;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::slice::Iter;
 use std::iter::Peekable;
+use std::mem;
 use std::cell::Cell;
 
 // Internal
 use INTERNAL_ERROR_MSG;
-use INVALID_UTF8;
 use SubCommand;
 use app::App;
 use app::help::Help;
+use app::limits::ParseLimits;
+use app::man::ManGen;
 use app::meta::AppMeta;
 use app::settings::AppFlags;
-use args::{AnyArg, Arg, ArgGroup, ArgMatcher, Base, FlagBuilder, OptBuilder, PosBuilder, Switched};
+use args::{AnyArg, Arg, ArgGroup, ArgKind, ArgMatcher, ArgMatches, Base, FlagBuilder,
+           MatchedArg, OptBuilder, PosBuilder, Switched, TokenKind};
 use args::settings::ArgSettings;
 use completions::ComplGen;
 use errors::{Error, ErrorKind};
 use errors::Result as ClapResult;
-use fmt::ColorWhen;
+use fmt::{ColorWhen, Colors};
 use osstringext::OsStrExt2;
 use completions::Shell;
 use suggestions;
@@ -33,6 +37,7 @@ use app::settings::AppSettings as AS;
 use app::validator::Validator;
 use app::usage;
 use map::{self, VecMap};
+use output::{self, OutputKind};
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 #[doc(hidden)]
@@ -118,14 +123,7 @@ where
         use std::error::Error;
 
         let out_dir = PathBuf::from(od);
-        let name = &*self.meta.bin_name.as_ref().unwrap().clone();
-        let file_name = match for_shell {
-            Shell::Bash => format!("{}.bash", name),
-            Shell::Fish => format!("{}.fish", name),
-            Shell::Zsh => format!("_{}", name),
-            Shell::PowerShell => format!("_{}.ps1", name),
-            Shell::Elvish => format!("{}.elv", name),
-        };
+        let file_name = self.completions_file_name(for_shell);
 
         let mut file = match File::create(out_dir.join(file_name)) {
             Err(why) => panic!("couldn't create completion file: {}", why.description()),
@@ -134,6 +132,49 @@ where
         self.gen_completions_to(for_shell, &mut file)
     }
 
+    pub fn gen_completions_to_dir(&mut self, dir: &Path, shells: &[Shell]) -> io::Result<()> {
+        try!(fs::create_dir_all(dir));
+        for &shell in shells {
+            let file_name = self.completions_file_name(shell);
+            let mut file = BufWriter::new(try!(File::create(dir.join(file_name))));
+            self.gen_completions_to(shell, &mut file);
+            try!(file.flush());
+        }
+        Ok(())
+    }
+
+    pub fn gen_manpage<W: Write>(&mut self, buf: &mut W) -> io::Result<()> {
+        if !self.is_set(AS::Propagated) {
+            self.propagate_help_version();
+            self.build_bin_names();
+            self.propagate_globals();
+            self.propagate_settings();
+            self.set(AS::Propagated);
+        }
+
+        ManGen::new(self).generate_to(buf)
+    }
+
+    pub fn gen_manpage_to(&mut self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            try!(fs::create_dir_all(dir));
+        }
+        let mut file = BufWriter::new(try!(File::create(path)));
+        try!(self.gen_manpage(&mut file));
+        file.flush()
+    }
+
+    fn completions_file_name(&self, for_shell: Shell) -> String {
+        let name = self.meta.bin_name.as_ref().unwrap();
+        match for_shell {
+            Shell::Bash => format!("{}.bash", name),
+            Shell::Fish => format!("{}.fish", name),
+            Shell::Zsh => format!("_{}", name),
+            Shell::PowerShell => format!("_{}.ps1", name),
+            Shell::Elvish => format!("{}.elv", name),
+        }
+    }
+
     #[inline]
     fn app_debug_asserts(&self) -> bool {
         assert!(self.verify_positionals());
@@ -182,25 +223,37 @@ where
                 l
             );
         }
-        if let Some(s) = a.s.short {
+        for &s in a.s.short.iter().chain(a.s.shorts.iter().flat_map(|v| v.iter())) {
             assert!(
                 !self.contains_short(s),
                 "Argument short must be unique\n\n\t-{} is already in use",
                 s
             );
         }
-        let i = if a.index.is_none() {
-            (self.positionals.len() + 1)
-        } else {
-            a.index.unwrap() as usize
-        };
-        assert!(
-            !self.positionals.contains_key(i),
-            "Argument \"{}\" has the same index as another positional \
-             argument\n\n\tPerhaps try .multiple(true) to allow one positional argument \
-             to take multiple values",
-            a.b.name
-        );
+        if a.index.is_some() || (a.s.short.is_none() && a.s.long.is_none()) {
+            let i = if a.index.is_none() {
+                (self.positionals.len() + 1)
+            } else {
+                a.index.unwrap() as usize
+            };
+            assert!(
+                !self.positionals.contains_key(i),
+                "Argument \"{}\" has the same index ({}) as another positional \
+                 argument\n\n\tPerhaps try .multiple(true) to allow one positional argument \
+                 to take multiple values",
+                a.b.name,
+                i
+            );
+        }
+        if a.is_set(ArgSettings::Count) {
+            assert!(
+                (a.s.short.is_some() || a.s.long.is_some()) && a.index.is_none()
+                    && !a.is_set(ArgSettings::TakesValue),
+                "Argument \"{}\" is a counter, so it must be a flag with a short or long \
+                 and can't take a value",
+                a.b.name
+            );
+        }
         assert!(
             !(a.is_set(ArgSettings::Required) && a.is_set(ArgSettings::Global)),
             "Global arguments cannot be required.\n\n\t'{}' is marked as \
@@ -376,6 +429,8 @@ where
         subcmd.p.meta.term_w = self.meta.term_w;
         if subcmd.p.meta.name == "help" {
             self.unset(AS::NeedsSubcommandHelp);
+        } else if subcmd.p.meta.name == "version" {
+            self.unset(AS::NeedsSubcommandVersion);
         }
 
         self.subcommands.push(subcmd);
@@ -405,12 +460,33 @@ where
                 }
                 if gv && sc.p.meta.version.is_none() && self.meta.version.is_some() {
                     sc.p.set(AS::GlobalVersion);
-                    sc.p.meta.version = Some(self.meta.version.unwrap());
+                }
+                // Subcommands inherit the version and author unless they have their own
+                if sc.p.meta.version.is_none() {
+                    sc.p.meta.version = self.meta.version;
+                }
+                if sc.p.meta.author.is_none() {
+                    sc.p.meta.author = self.meta.author;
                 }
                 sc.p.settings = sc.p.settings | self.g_settings;
                 sc.p.g_settings = sc.p.g_settings | self.g_settings;
                 sc.p.meta.term_w = self.meta.term_w;
                 sc.p.meta.max_w = self.meta.max_w;
+                if sc.p.meta.help_indent.is_none() {
+                    sc.p.meta.help_indent = self.meta.help_indent;
+                }
+                if sc.p.meta.palette.is_none() {
+                    sc.p.meta.palette = self.meta.palette;
+                }
+                if sc.p.meta.sink.is_none() {
+                    sc.p.meta.sink = self.meta.sink.clone();
+                }
+                if self.meta.chat_color {
+                    sc.p.meta.chat_color = true;
+                }
+                if sc.p.meta.limits == ParseLimits::default() {
+                    sc.p.meta.limits = self.meta.limits;
+                }
             }
             sc.p.propagate_settings();
         }
@@ -516,16 +592,18 @@ where
         // Because you must wait until all arguments have been supplied, this is the first chance
         // to make assertions on positional argument indexes
         //
-        // First we verify that the index highest supplied index, is equal to the number of
-        // positional arguments to verify there are no gaps (i.e. supplying an index of 1 and 3
-        // but no 2)
-        if let Some((idx, p)) = self.positionals.iter().rev().next() {
-            assert!(
-                !(idx != self.positionals.len()),
-                "Found positional argument \"{}\" whose index is {} but there \
-                 are only {} positional arguments defined",
+        // First we verify that the indices are contiguous starting at 1, i.e. there are no gaps
+        // such as supplying an index of 1 and 3 but no 2
+        if let Some(missing) =
+            (1..self.positionals.len() + 1).find(|&i| !self.positionals.contains_key(i))
+        {
+            let (idx, p) = self.positionals.iter().rev().next().unwrap();
+            panic!(
+                "Found positional argument \"{}\" whose index is {} but there is no positional \
+                 argument with index {} ({} positional arguments are defined)",
                 p.b.name,
                 idx,
+                missing,
                 self.positionals.len()
             );
         }
@@ -655,6 +733,30 @@ where
         }
     }
 
+    // Records whether each matched arg is a flag, option or positional, including those of any
+    // subcommand. Args which aren't defined here (such as the values of an external subcommand)
+    // are treated as positionals when they have values.
+    pub fn set_arg_kinds(&self, matches: &mut ArgMatches<'a>) {
+        for (name, ma) in matches.args.iter_mut() {
+            ma.kind = if self.positionals.values().any(|p| p.b.name == *name) {
+                ArgKind::Positional
+            } else if self.opts.iter().any(|o| o.b.name == *name) {
+                ArgKind::Opt
+            } else if self.flags.iter().any(|f| f.b.name == *name) || ma.vals.is_empty() {
+                ArgKind::Flag
+            } else {
+                ArgKind::Positional
+            };
+        }
+        if let Some(ref mut sc) = matches.subcommand {
+            if let Some(sc_p) = self.subcommands.iter().find(|s| s.p.meta.name == sc.name) {
+                sc_p.p.set_arg_kinds(&mut sc.matches);
+            } else {
+                Parser::default().set_arg_kinds(&mut sc.matches);
+            }
+        }
+    }
+
     // Checks if the arg matches a subcommand name, or any of it's aliases (if defined)
     fn possible_subcommand(&self, arg_os: &OsStr) -> (bool, Option<&str>) {
         #[cfg(not(any(target_os = "windows", target_arch = "wasm32")))]
@@ -676,6 +778,19 @@ where
             if let Some(sc) = find_subcmd!(self, arg_os) {
                 return (true, Some(&sc.p.meta.name));
             }
+            if self.is_set(AS::CaseInsensitiveSubcommands) {
+                if let Some(arg) = arg_os.to_str() {
+                    let sc = self.subcommands.iter().find(|s| {
+                        s.p.meta.name.eq_ignore_ascii_case(arg)
+                            || s.p.meta.aliases.as_ref().map_or(false, |als| {
+                                als.iter().any(|&(a, _)| a.eq_ignore_ascii_case(arg))
+                            })
+                    });
+                    if let Some(sc) = sc {
+                        return (true, Some(&sc.p.meta.name));
+                    }
+                }
+            }
         } else {
             let v = self.subcommands
                 .iter()
@@ -707,7 +822,11 @@ where
         (false, None)
     }
 
-    fn parse_help_subcommand<I, T>(&self, it: &mut I) -> ClapResult<ParseResult<'a>>
+    fn parse_help_subcommand<I, T>(
+        &self,
+        matcher: &mut ArgMatcher<'a>,
+        it: &mut I,
+    ) -> ClapResult<ParseResult<'a>>
     where
         I: Iterator<Item = T>,
         T: Into<OsString>,
@@ -733,6 +852,7 @@ where
                     .map(|sc| &sc.p)
                 {
                     sc = c;
+                    matcher.token(Some(TokenKind::Subcommand));
                     if i == cmds.len() - 1 {
                         break;
                     }
@@ -748,6 +868,7 @@ where
                     .map(|sc| &sc.p)
                 {
                     sc = c;
+                    matcher.token(Some(TokenKind::Subcommand));
                     if i == cmds.len() - 1 {
                         break;
                     }
@@ -888,6 +1009,7 @@ where
             {
                 debugln!("Parser::get_matches_with: setting TrailingVals=true");
                 self.set(AS::TrailingValues);
+                matcher.token(None);
                 continue;
             }
 
@@ -905,9 +1027,15 @@ where
                                 sc_name
                             );
                             if is_match {
+                                matcher.token(Some(TokenKind::Subcommand));
                                 let sc_name = sc_name.expect(INTERNAL_ERROR_MSG);
-                                if sc_name == "help" && self.is_set(AS::NeedsSubcommandHelp) {
-                                    self.parse_help_subcommand(it)?;
+                                if sc_name == "help" && self.is_set(AS::NeedsSubcommandHelp)
+                                    && !self.is_set(AS::DisableHelpSubcommand)
+                                {
+                                    self.parse_help_subcommand(matcher, it)?;
+                                }
+                                if sc_name == "version" && self.is_set(AS::NeedsSubcommandVersion) {
+                                    return Err(self._version(true));
                                 }
                                 subcmd_name = Some(sc_name.to_owned());
                                 break;
@@ -935,8 +1063,13 @@ where
                             needs_val_of
                         );
                         match needs_val_of {
-                            ParseResult::Flag | ParseResult::Opt(..) | ParseResult::ValuesDone => {
-                                continue
+                            ParseResult::Flag => {
+                                matcher.token(Some(TokenKind::Flag));
+                                continue;
+                            }
+                            ParseResult::Opt(..) | ParseResult::ValuesDone => {
+                                matcher.token(Some(TokenKind::Opt));
+                                continue;
                             }
                             _ => (),
                         }
@@ -963,8 +1096,13 @@ where
                                     ));
                                 }
                             }
-                            ParseResult::Opt(..) | ParseResult::Flag | ParseResult::ValuesDone => {
-                                continue
+                            ParseResult::Flag => {
+                                matcher.token(Some(TokenKind::Flag));
+                                continue;
+                            }
+                            ParseResult::Opt(..) | ParseResult::ValuesDone => {
+                                matcher.token(Some(TokenKind::Opt));
+                                continue;
                             }
                             _ => (),
                         }
@@ -978,6 +1116,7 @@ where
                             .expect(INTERNAL_ERROR_MSG);
                         // get the OptBuilder so we can check the settings
                         needs_val_of = self.add_val_to_arg(arg, &arg_os, matcher)?;
+                        matcher.token(Some(TokenKind::Value));
                         // get the next value from the iterator
                         continue;
                     }
@@ -1073,6 +1212,7 @@ where
                     self.cache = Some(p.b.name);
                 }
                 let _ = self.add_val_to_arg(p, &arg_os, matcher)?;
+                matcher.token(Some(TokenKind::Positional));
 
                 matcher.inc_occurrence_of(p.b.name);
                 let _ = self.groups_for_arg(p.b.name)
@@ -1100,6 +1240,7 @@ where
                 };
 
                 // Collect the external subcommand args
+                matcher.token(Some(TokenKind::Subcommand));
                 let mut sc_m = ArgMatcher::new();
                 while let Some(v) = it.next() {
                     let a = v.into();
@@ -1110,6 +1251,7 @@ where
                         ));
                     }
                     sc_m.add_val_to("", &a);
+                    matcher.token(Some(TokenKind::Value));
                 }
 
                 matcher.subcommand(SubCommand {
@@ -1157,6 +1299,9 @@ where
         }
 
         if !sc_is_external {
+            // With ArgsNegateSubcommands, valid args in place of a subcommand satisfy the app
+            let args_negate_sc =
+                self.is_set(AS::ArgsNegateSubcommands) && self.is_set(AS::ValidArgFound);
             if let Some(ref pos_sc_name) = subcmd_name {
                 let sc_name = {
                     find_subcmd!(self, pos_sc_name)
@@ -1167,7 +1312,7 @@ where
                         .clone()
                 };
                 self.parse_subcommand(&*sc_name, matcher, it)?;
-            } else if self.is_set(AS::SubcommandRequired) {
+            } else if self.is_set(AS::SubcommandRequired) && !args_negate_sc {
                 let bn = self.meta.bin_name.as_ref().unwrap_or(&self.meta.name);
                 return Err(Error::missing_subcommand(
                     bn,
@@ -1304,7 +1449,7 @@ where
             .iter_mut()
             .find(|s| s.p.meta.name == sc_name)
         {
-            let mut sc_matcher = ArgMatcher::new();
+            let mut sc_matcher = matcher.for_subcommand();
             // bin_name should be parent's bin_name + [<reqs>] + the sc's name separated by
             // a space
             sc.p.meta.usage = Some(format!(
@@ -1332,7 +1477,18 @@ where
                 sc.p.meta.name
             );
             debugln!("Parser::parse_subcommand: sc settings={:#?}", sc.p.settings);
-            sc.p.get_matches_with(&mut sc_matcher, it)?;
+            // The subcommand's tokens are kept even if it fails to parse
+            let res = sc.p.get_matches_with(&mut sc_matcher, it);
+            matcher.take_tokens(&mut sc_matcher);
+            res?;
+            let inherited = self.global_args
+                .iter()
+                .filter_map(|a| match matcher.get(a.b.name) {
+                    Some(ma) if ma.occurs > 0 => Some((a.b.name, ma.clone())),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            sc.p.validate_inherited_globals(&mut sc_matcher, &inherited)?;
             matcher.subcommand(SubCommand {
                 name: sc.p.meta.name.clone(),
                 matches: sc_matcher.into(),
@@ -1341,6 +1497,39 @@ where
         Ok(())
     }
 
+    // Global args used before the subcommand only reach its matches once parsing is done, so
+    // their conflicts and requirements are checked against each subcommand level here
+    fn validate_inherited_globals(
+        &mut self,
+        matcher: &mut ArgMatcher<'a>,
+        inherited: &[(&'a str, MatchedArg)],
+    ) -> ClapResult<()> {
+        debugln!("Parser::validate_inherited_globals;");
+        if inherited.is_empty() {
+            return Ok(());
+        }
+        let mut names = vec![];
+        for &(name, ref ma) in inherited {
+            if matcher.get(name).map_or(true, |m| m.occurs == 0) {
+                matcher.0.args.insert(name, ma.clone());
+                names.push(name);
+            }
+        }
+        Validator::new(self).validate_inherited(&names, matcher)?;
+        if let Some(ref mut sub) = matcher.0.subcommand {
+            if let Some(sc) = self.subcommands
+                .iter_mut()
+                .find(|s| s.p.meta.name == sub.name)
+            {
+                let mut sc_matcher = ArgMatcher::from(mem::replace(&mut sub.matches, ArgMatches::new()));
+                let res = sc.p.validate_inherited_globals(&mut sc_matcher, inherited);
+                mem::swap(&mut sc_matcher.0, &mut sub.matches);
+                res?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn groups_for_arg(&self, name: &str) -> Option<Vec<&'a str>> {
         debugln!("Parser::groups_for_arg: name={}", name);
 
@@ -1458,8 +1647,14 @@ where
             };
             self.flags.push(arg);
         }
-        if !self.subcommands.is_empty() && !self.is_set(AS::DisableHelpSubcommand)
+        // Unless one was declared, a subcommand named "version" is the one built below
+        let needs_sc_version = self.is_set(AS::NeedsSubcommandVersion);
+        if self.subcommands
+            .iter()
+            .any(|s| !(needs_sc_version && s.p.meta.name == "version"))
+            && !self.is_set(AS::DisableHelpSubcommand)
             && self.is_set(AS::NeedsSubcommandHelp)
+            && !self.subcommands.iter().any(|s| s.p.meta.name == "help")
         {
             debugln!("Parser::create_help_and_version: Building help");
             self.subcommands.push(
@@ -1467,6 +1662,17 @@ where
                     .about("Prints this message or the help of the given subcommand(s)"),
             );
         }
+        if (self.is_set(AS::VersionSubcommand) || self.is_set(AS::HiddenVersionSubcommand))
+            && !self.is_set(AS::DisableVersion) && needs_sc_version
+            && !self.subcommands.iter().any(|s| s.p.meta.name == "version")
+        {
+            debugln!("Parser::create_help_and_version: Building version");
+            let mut sc = App::new("version").about("Prints version information");
+            if self.is_set(AS::HiddenVersionSubcommand) {
+                sc.p.set(AS::Hidden);
+            }
+            self.subcommands.push(sc);
+        }
     }
 
     // Retrieves the names of all args the user has supplied thus far, except required ones
@@ -1477,11 +1683,13 @@ where
             "Parser::check_for_help_and_version_str: Checking if --{} is help or version...",
             arg.to_str().unwrap()
         );
-        if arg == "help" && self.is_set(AS::NeedsLongHelp) {
+        if arg == "help" && self.is_set(AS::NeedsLongHelp) && !self.is_set(AS::DisableHelpFlags) {
             sdebugln!("Help");
             return Err(self._help(true));
         }
-        if arg == "version" && self.is_set(AS::NeedsLongVersion) {
+        if arg == "version" && self.is_set(AS::NeedsLongVersion)
+            && !self.is_set(AS::DisableVersion)
+        {
             sdebugln!("Version");
             return Err(self._version(true));
         }
@@ -1497,13 +1705,13 @@ where
             arg
         );
         if let Some(h) = self.help_short {
-            if arg == h && self.is_set(AS::NeedsLongHelp) {
+            if arg == h && self.is_set(AS::NeedsLongHelp) && !self.is_set(AS::DisableHelpFlags) {
                 sdebugln!("Help");
                 return Err(self._help(false));
             }
         }
         if let Some(v) = self.version_short {
-            if arg == v && self.is_set(AS::NeedsLongVersion) {
+            if arg == v && self.is_set(AS::NeedsLongVersion) && !self.is_set(AS::DisableVersion) {
                 sdebugln!("Version");
                 return Err(self._version(false));
             }
@@ -1548,6 +1756,18 @@ where
 
     fn _version(&self, use_long: bool) -> Error {
         debugln!("Parser::_version: ");
+        if let Some(ref sink) = self.meta.sink {
+            let mut buf = vec![];
+            if let Err(e) = self.print_version(&mut buf, use_long) {
+                return e;
+            }
+            output::write_lines(&**sink, &*String::from_utf8_lossy(&buf), OutputKind::Info);
+            return Error {
+                message: String::new(),
+                kind: ErrorKind::VersionDisplayed,
+                info: None,
+            };
+        }
         let out = io::stdout();
         let mut buf_w = BufWriter::new(out.lock());
         match self.print_version(&mut buf_w, use_long) {
@@ -1587,6 +1807,17 @@ where
             sdebugln!("No");
             full_arg.trim_left_matches(b'-')
         };
+        let arg = if self.is_set(AS::NormalizeLongSeparators)
+            && find_opt_by_long!(@os self, arg).is_none()
+            && find_flag_by_long!(@os self, arg).is_none()
+        {
+            match self.find_normalized_long(arg) {
+                Some(long) => OsStr::new(long),
+                None => arg,
+            }
+        } else {
+            arg
+        };
 
         if let Some(opt) = find_opt_by_long!(@os self, arg) {
             debugln!(
@@ -1610,13 +1841,45 @@ where
             // so this is the first point to check
             self.check_for_help_and_version_str(arg)?;
 
-            self.parse_flag(flag, matcher)?;
+            match val {
+                Some(v) if flag.is_set(ArgSettings::Negatable) => {
+                    if v == OsStr::new("true") {
+                        self.parse_flag(flag, matcher)?;
+                    } else if v == OsStr::new("false") {
+                        matcher.negate(flag.b.name);
+                    } else {
+                        return Err(Error::invalid_value(
+                            v.to_string_lossy(),
+                            &["true", "false"],
+                            flag,
+                            &*usage::create_error_usage(self, matcher, None),
+                            self.color(),
+                        ));
+                    }
+                }
+                _ => {
+                    self.parse_flag(flag, matcher)?;
+                }
+            }
 
             // Handle conflicts, requirements, etc.
             if self.cache.map_or(true, |name| name != flag.b.name) {
                 self.cache = Some(flag.b.name);
             }
 
+            return Ok(ParseResult::Flag);
+        } else if let Some(flag) = Parser::find_negated_flag(&self.flags, arg) {
+            debugln!(
+                "Parser::parse_long_arg: Found negation of flag '{}'",
+                flag.to_string()
+            );
+            self.settings.set(AS::ValidArgFound);
+            matcher.negate(flag.b.name);
+
+            if self.cache.map_or(true, |name| name != flag.b.name) {
+                self.cache = Some(flag.b.name);
+            }
+
             return Ok(ParseResult::Flag);
         } else if self.is_set(AS::AllowLeadingHyphen) {
             return Ok(ParseResult::MaybeHyphenValue);
@@ -1626,10 +1889,12 @@ where
 
         debugln!("Parser::parse_long_arg: Didn't match anything");
 
-        let args_rest: Vec<_> = it.map(|x| x.clone().into()).collect();
-        let args_rest2: Vec<_> = args_rest.iter().map(|x| x.to_str().expect(INVALID_UTF8)).collect();
+        // Use lossy conversions here, as invalid UTF-8 must result in an error rather than a panic
+        let args_rest: Vec<OsString> = it.map(|x| x.clone().into()).collect();
+        let args_rest: Vec<_> = args_rest.iter().map(|x| x.to_string_lossy()).collect();
+        let args_rest2: Vec<_> = args_rest.iter().map(|x| &**x).collect();
         self.did_you_mean_error(
-            arg.to_str().expect(INVALID_UTF8),
+            &*arg.to_string_lossy(),
             matcher,
             &args_rest2[..]
         ).map(|_| ParseResult::NotFound)
@@ -1856,6 +2121,12 @@ where
             }
         }
 
+        if let Some(max) = self.meta.limits.max_values {
+            if matcher.get(arg.name()).map_or(0, |ma| ma.vals.len()) >= max {
+                return Err(Error::limit_exceeded("values", max, Some(arg), self.color()));
+            }
+        }
+
         matcher.add_val_to(arg.name(), v);
         matcher.add_index_to(arg.name(), self.cur_idx.get());
 
@@ -1872,6 +2143,38 @@ where
         Ok(ParseResult::ValuesDone)
     }
 
+    // Finds the declared long or alias which matches `arg` when `_` and `-` are treated as the
+    // same character
+    fn find_normalized_long(&self, arg: &OsStr) -> Option<&'b str> {
+        debugln!("Parser::find_normalized_long: arg={:?}", arg);
+        let arg = arg.to_string_lossy().replace('_', "-");
+        self.flags
+            .iter()
+            .map(|f| &f.s)
+            .chain(self.opts.iter().map(|o| &o.s))
+            .flat_map(|s| {
+                s.long
+                    .into_iter()
+                    .chain(s.aliases.iter().flat_map(|a| a.iter().map(|&(n, _)| n)))
+            })
+            .find(|l| l.replace('_', "-") == arg)
+    }
+
+    // Finds the negatable flag `arg` turns off if it's of the form `no-<long>`
+    fn find_negated_flag<'z>(
+        flags: &'z [FlagBuilder<'a, 'b>],
+        arg: &OsStr,
+    ) -> Option<&'z FlagBuilder<'a, 'b>> {
+        if !arg.starts_with(b"no-") {
+            return None;
+        }
+        let long = arg.split_at(3).1;
+        flags
+            .iter()
+            .filter(|f| f.b.is_set(ArgSettings::Negatable))
+            .find(|f| f.s.long.map_or(false, |l| OsStr::new(l) == long))
+    }
+
     fn parse_flag(
         &self,
         flag: &FlagBuilder<'a, 'b>,
@@ -1891,7 +2194,13 @@ where
 
     fn did_you_mean_error(&self, arg: &str, matcher: &mut ArgMatcher<'a>, args_rest: &[&str]) -> ClapResult<()> {
         // Didn't match a flag or option
-        let suffix = suggestions::did_you_mean_flag_suffix(arg, &args_rest, longs!(self), &self.subcommands);
+        let suffix = suggestions::did_you_mean_flag_suffix(
+            arg,
+            &args_rest,
+            longs!(self),
+            &self.subcommands,
+            self.color(),
+        );
 
         // Add the arg to the matches to build a proper usage string
         if let Some(name) = suffix.1 {
@@ -1969,7 +2278,8 @@ where
                     debugln!("Parser::add_defaults:iter:{}: has default vals", $a.b.name);
                     if $m.get($a.b.name).map(|ma| ma.vals.len()).map(|len| len == 0).unwrap_or(false) {
                         debugln!("Parser::add_defaults:iter:{}: has no user defined vals", $a.b.name);
-                        $_self.add_val_to_arg($a, OsStr::new(val), $m)?;
+                        let val = $_self.expand_default_val($a, OsStr::new(val))?;
+                        $_self.add_val_to_arg($a, &*val, $m)?;
 
                         if $_self.cache.map_or(true, |name| name != $a.name()) {
                             $_self.cache = Some($a.name());
@@ -1979,7 +2289,8 @@ where
                     } else {
                         debugln!("Parser::add_defaults:iter:{}: wasn't used", $a.b.name);
 
-                        $_self.add_val_to_arg($a, OsStr::new(val), $m)?;
+                        let val = $_self.expand_default_val($a, OsStr::new(val))?;
+                        $_self.add_val_to_arg($a, &*val, $m)?;
 
                         if $_self.cache.map_or(true, |name| name != $a.name()) {
                             $_self.cache = Some($a.name());
@@ -2005,7 +2316,8 @@ where
                                 false
                             };
                             if add {
-                                $_self.add_val_to_arg($a, OsStr::new(default), $m)?;
+                                let default = $_self.expand_default_val($a, OsStr::new(default))?;
+                                $_self.add_val_to_arg($a, &*default, $m)?;
                                 if $_self.cache.map_or(true, |name| name != $a.name()) {
                                     $_self.cache = Some($a.name());
                                 }
@@ -2036,19 +2348,53 @@ where
         Ok(())
     }
 
+    // Expands any `${VAR}` in a default value when AppSettings::ExpandDefaultEnvVars (or
+    // RequireDefaultEnvVars) is set, otherwise the value is returned as is
+    fn expand_default_val<A>(&self, a: &A, val: &OsStr) -> ClapResult<OsString>
+    where
+        A: AnyArg<'a, 'b> + Display,
+    {
+        let strict = self.is_set(AS::RequireDefaultEnvVars);
+        if !strict && !self.is_set(AS::ExpandDefaultEnvVars) {
+            return Ok(val.to_os_string());
+        }
+        let mut rest = match val.to_str() {
+            Some(s) => s,
+            None => return Ok(val.to_os_string()),
+        };
+        let mut expanded = OsString::new();
+        while let Some(start) = rest.find("${") {
+            let end = match rest[start..].find('}') {
+                Some(i) => start + i,
+                None => break,
+            };
+            expanded.push(&rest[..start]);
+            let var = &rest[start + 2..end];
+            debugln!("Parser::expand_default_val: expanding {}", var);
+            if let Some(v) = env::var_os(var) {
+                expanded.push(v);
+            } else if strict {
+                return Err(Error::value_validation(
+                    Some(a),
+                    format!(
+                        "the environment variable '{}' used by the default value isn't set",
+                        var
+                    ),
+                    self.color(),
+                ));
+            }
+            rest = &rest[end + 1..];
+        }
+        expanded.push(rest);
+        Ok(expanded)
+    }
+
     pub fn add_env(&mut self, matcher: &mut ArgMatcher<'a>) -> ClapResult<()> {
         macro_rules! add_val {
             ($_self:ident, $a:ident, $m:ident) => {
                 if let Some(ref val) = $a.v.env {
-                    if $m.get($a.b.name).map(|ma| ma.vals.len()).map(|len| len == 0).unwrap_or(false) {
-                        if let Some(ref val) = val.1 {
-                            $_self.add_val_to_arg($a, OsStr::new(val), $m)?;
-
-                            if $_self.cache.map_or(true, |name| name != $a.name()) {
-                                $_self.cache = Some($a.name());
-                            }
-                        }
-                    } else {
+                    // Values given on the command line always win over the environment
+                    if $m.get($a.b.name).map_or(true, |ma| ma.vals.is_empty()) {
                         if let Some(ref val) = val.1 {
                             $_self.add_val_to_arg($a, OsStr::new(val), $m)?;
 
@@ -2080,10 +2426,10 @@ where
 
     // Should we color the output? None=determined by output location, true=yes, false=no
     #[doc(hidden)]
-    pub fn color(&self) -> ColorWhen {
+    pub fn color(&self) -> Colors {
         debugln!("Parser::color;");
         debug!("Parser::color: Color setting...");
-        if self.is_set(AS::ColorNever) {
+        let when = if self.is_set(AS::ColorNever) {
             sdebugln!("Never");
             ColorWhen::Never
         } else if self.is_set(AS::ColorAlways) {
@@ -2092,6 +2438,10 @@ where
         } else {
             sdebugln!("Auto");
             ColorWhen::Auto
+        };
+        Colors {
+            when: when,
+            palette: self.meta.palette.unwrap_or_default(),
         }
     }
 
@@ -0,0 +1,110 @@
+// Std
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+
+// Internal
+use app::limits::ParseLimits;
+use errors::{Error, Result as ClapResult};
+use fmt::Colors;
+
+// Replaces each `@path` argument with the whitespace separated arguments read from `path`, which
+// are expanded in turn. Nothing after a `--` is expanded. A file which includes itself, directly
+// or through other files, is an error rather than being expanded forever.
+//
+// This is the first rewrite applied to the arguments, and `App::replace` replacements are applied
+// to the result. The `ParseLimits` are checked as each argument is added, so expansion stops as
+// soon as they're exceeded.
+//
+// Since a few small files can reference each other enough to expand into millions of arguments,
+// expansion is always bounded: files may only be nested `MAX_DEPTH` deep, each may hold at most
+// `MAX_FILE_LEN` bytes, and at most `MAX_TOKENS` tokens (or `ParseLimits::max_tokens` if set) are
+// read across all files, counting the `@path` tokens themselves.
+const MAX_DEPTH: usize = 16;
+const MAX_FILE_LEN: u64 = 1 << 20;
+const MAX_TOKENS: usize = 1 << 16;
+
+pub fn expand<I, T>(itr: I, limits: &ParseLimits, color: Colors) -> ClapResult<Vec<OsString>>
+where
+    I: Iterator<Item = T>,
+    T: Into<OsString>,
+{
+    let mut args = Vec::new();
+    let mut trailing = false;
+    let mut read = 0;
+    for arg in itr {
+        try!(expand_arg(
+            arg.into(),
+            &mut args,
+            &mut vec![],
+            &mut trailing,
+            &mut read,
+            limits,
+            color
+        ));
+    }
+    Ok(args)
+}
+
+fn expand_arg(
+    arg: OsString,
+    args: &mut Vec<OsString>,
+    open: &mut Vec<PathBuf>,
+    trailing: &mut bool,
+    read: &mut usize,
+    limits: &ParseLimits,
+    color: Colors,
+) -> ClapResult<()> {
+    let path = match arg.to_str() {
+        Some(s) if !*trailing && s.starts_with('@') && s.len() > 1 => PathBuf::from(&s[1..]),
+        _ => {
+            *trailing = *trailing || arg == "--";
+            try!(limits.check(args, &arg, color));
+            args.push(arg);
+            return Ok(());
+        }
+    };
+    debugln!("response_files::expand_arg: path={:?}", path);
+    // A file which (indirectly) includes itself would never finish expanding
+    if open.contains(&path) {
+        return Err(Error::response_file(
+            &*path.to_string_lossy(),
+            "it includes itself",
+            color,
+        ));
+    }
+    if open.len() >= MAX_DEPTH {
+        return Err(Error::response_file(
+            &*path.to_string_lossy(),
+            format!("it's nested more than {} response files deep", MAX_DEPTH),
+            color,
+        ));
+    }
+    // One byte past the limit is read so that an oversized file can be told apart from one which
+    // is exactly at it
+    let mut contents = String::new();
+    if let Err(e) = File::open(&path)
+        .and_then(|f| f.take(MAX_FILE_LEN + 1).read_to_string(&mut contents))
+    {
+        return Err(Error::response_file(&*path.to_string_lossy(), e, color));
+    }
+    if contents.len() as u64 > MAX_FILE_LEN {
+        return Err(Error::response_file(
+            &*path.to_string_lossy(),
+            format!("it's larger than {} bytes", MAX_FILE_LEN),
+            color,
+        ));
+    }
+    let max_read = limits.max_tokens.unwrap_or(MAX_TOKENS);
+    open.push(path);
+    for token in contents.split_whitespace() {
+        *read += 1;
+        if *read > max_read {
+            return Err(Error::limit_exceeded("arguments", max_read, None, color));
+        }
+        try!(expand_arg(token.into(), args, open, trailing, read, limits, color));
+    }
+    open.pop();
+    Ok(())
+}
@@ -48,6 +48,14 @@ bitflags! {
         const CONTAINS_LAST        = 1 << 39;
         const ARGS_OVERRIDE_SELF   = 1 << 40;
         const DISABLE_HELP_FLAGS   = 1 << 41;
+        const EXPAND_DEFAULT_ENV   = 1 << 42;
+        const REQUIRE_DEFAULT_ENV  = 1 << 43;
+        const CI_SUBCOMMANDS       = 1 << 44;
+        const RESPONSE_FILES       = 1 << 45;
+        const VERSION_SC           = 1 << 46;
+        const HIDDEN_VERSION_SC    = 1 << 47;
+        const NEEDS_SC_VERSION     = 1 << 48;
+        const NORMALIZE_LONG_SEPS  = 1 << 49;
     }
 }
 
@@ -64,7 +72,7 @@ impl Default for AppFlags {
     fn default() -> Self {
         AppFlags(
             Flags::NEEDS_LONG_VERSION | Flags::NEEDS_LONG_HELP | Flags::NEEDS_SC_HELP
-                | Flags::UTF8_NONE | Flags::COLOR_AUTO,
+                | Flags::NEEDS_SC_VERSION | Flags::UTF8_NONE | Flags::COLOR_AUTO,
         )
     }
 }
@@ -116,7 +124,15 @@ impl AppFlags {
         Propagated => Flags::PROPAGATED,
         ValidArgFound => Flags::VALID_ARG_FOUND,
         InferSubcommands => Flags::INFER_SUBCOMMANDS,
-        ContainsLast => Flags::CONTAINS_LAST
+        ContainsLast => Flags::CONTAINS_LAST,
+        ExpandDefaultEnvVars => Flags::EXPAND_DEFAULT_ENV,
+        RequireDefaultEnvVars => Flags::REQUIRE_DEFAULT_ENV,
+        CaseInsensitiveSubcommands => Flags::CI_SUBCOMMANDS,
+        AllowResponseFiles => Flags::RESPONSE_FILES,
+        VersionSubcommand => Flags::VERSION_SC,
+        HiddenVersionSubcommand => Flags::HIDDEN_VERSION_SC,
+        NeedsSubcommandVersion => Flags::NEEDS_SC_VERSION,
+        NormalizeLongSeparators => Flags::NORMALIZE_LONG_SEPS
     }
 }
 
@@ -367,6 +383,43 @@ pub enum AppSettings {
     /// [`ArgMatches`]: ./struct.ArgMatches.html
     AllowExternalSubcommands,
 
+    /// Allows arguments to be read from response files. Any argument of the form `@path` is
+    /// replaced by the whitespace separated arguments read from the file at `path`, which may in
+    /// turn contain further `@path` arguments. This is useful for command lines which would
+    /// otherwise exceed the operating system's length limit.
+    ///
+    /// Arguments after a `--` are never treated as response files. A response file which can't be
+    /// read, or which includes itself (directly or through other response files), results in an
+    /// [`ErrorKind::Io`] error.
+    ///
+    /// Expansion is bounded even without any [`ParseLimits`]: response files may be nested at most
+    /// 16 deep and may each be at most 1 MiB, otherwise an [`ErrorKind::Io`] error results. At
+    /// most 65536 tokens (or [`ParseLimits::max_tokens`] if set) are read across all response
+    /// files, including the `@path` tokens themselves, before an [`ErrorKind::LimitExceeded`]
+    /// error results.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, AppSettings};
+    /// // args.txt contains "--world main --player Notch"
+    /// let m = App::new("myprog")
+    ///     .setting(AppSettings::AllowResponseFiles)
+    ///     .arg(Arg::with_name("world").long("world").takes_value(true))
+    ///     .arg(Arg::with_name("player").long("player").takes_value(true))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "@args.txt"
+    ///     ]);
+    ///
+    /// assert_eq!(m.value_of("world"), Some("main"));
+    /// assert_eq!(m.value_of("player"), Some("Notch"));
+    /// ```
+    /// [`ErrorKind::Io`]: ./enum.ErrorKind.html#variant.Io
+    /// [`ErrorKind::LimitExceeded`]: ./enum.ErrorKind.html#variant.LimitExceeded
+    /// [`ParseLimits`]: ./struct.ParseLimits.html
+    /// [`ParseLimits::max_tokens`]: ./struct.ParseLimits.html#method.max_tokens
+    AllowResponseFiles,
+
     /// Specifies that use of a valid [argument] negates [subcommands] being used after. By default
     /// `clap` allows arguments between subcommands such as
     /// `<cmd> [cmd_args] <cmd2> [cmd2_args] <cmd3> [cmd3_args]`. This setting disables that
@@ -377,6 +430,10 @@ pub enum AppSettings {
     /// * `<cmd> <cmd2> [cmd2_args]`
     /// * `<cmd> [cmd_args]`
     ///
+    /// When combined with [`AppSettings::SubcommandRequired`], supplying a valid argument also
+    /// satisfies the requirement, so the app may be run with either its own arguments or a
+    /// subcommand.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -385,6 +442,7 @@ pub enum AppSettings {
     ///     .setting(AppSettings::ArgsNegateSubcommands)
     /// # ;
     /// ```
+    /// [`AppSettings::SubcommandRequired`]: ./enum.AppSettings.html#variant.SubcommandRequired
     /// [subcommands]: ./struct.SubCommand.html
     /// [argument]: ./struct.Arg.html
     ArgsNegateSubcommands,
@@ -409,6 +467,28 @@ pub enum AppSettings {
     /// [`Arg::default_value`]: ./struct.Arg.html#method.default_value
     ArgRequiredElseHelp,
 
+    /// Matches [`SubCommand`] names and their [aliases] without regard to ASCII case, so `Build`
+    /// or `BUILD` are both accepted for a subcommand named `build`. The subcommand is always
+    /// reported under its declared name.
+    ///
+    /// This is useful for chat commands, where players rarely care about case.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, SubCommand, AppSettings};
+    /// let m = App::new("prog")
+    ///     .setting(AppSettings::CaseInsensitiveSubcommands)
+    ///     .subcommand(SubCommand::with_name("build"))
+    ///     .get_matches_from(vec![
+    ///         "prog", "Build"
+    ///     ]);
+    /// assert_eq!(m.subcommand_name(), Some("build"));
+    /// ```
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [aliases]: ./struct.App.html#method.alias
+    CaseInsensitiveSubcommands,
+
     /// Uses colorized help messages.
     ///
     /// **NOTE:** Must be compiled with the `color` cargo feature
@@ -613,8 +693,36 @@ pub enum AppSettings {
     /// [`SubCommand`]: ./struct.SubCommand.html
     DeriveDisplayOrder,
 
+    /// Expands `${VAR}` references in [`Arg::default_value`] and [`Arg::default_value_if`] from
+    /// the environment at the time the default is applied. A `$` which isn't followed by `{...}`
+    /// is left alone, and variables which aren't set expand to an empty string (see
+    /// [`AppSettings::RequireDefaultEnvVars`] to make that an error instead).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings};
+    /// # use std::env;
+    /// env::set_var("MY_APP_DIR", "/opt/app");
+    /// let m = App::new("myprog")
+    ///     .setting(AppSettings::ExpandDefaultEnvVars)
+    ///     .arg(Arg::with_name("config")
+    ///         .long("config")
+    ///         .default_value("${MY_APP_DIR}/config.toml"))
+    ///     .get_matches_from(vec![
+    ///         "myprog"
+    ///     ]);
+    /// assert_eq!(m.value_of("config"), Some("/opt/app/config.toml"));
+    /// ```
+    /// [`Arg::default_value`]: ./struct.Arg.html#method.default_value
+    /// [`Arg::default_value_if`]: ./struct.Arg.html#method.default_value_if
+    /// [`AppSettings::RequireDefaultEnvVars`]: ./enum.AppSettings.html#variant.RequireDefaultEnvVars
+    ExpandDefaultEnvVars,
+
     /// Specifies to use the version of the current command for all child [`SubCommand`]s.
-    /// (Defaults to `false`; subcommands have independent version strings from their parents.)
+    ///
+    /// **NOTE:** Child subcommands which don't set their own version inherit it anyway, so this
+    /// setting is only kept for backwards compatibility.
     ///
     /// **NOTE:** The version for the current command **and** this setting must be set **prior** to
     /// adding any child subcommands
@@ -652,6 +760,23 @@ pub enum AppSettings {
     /// This can be useful if there are many values, or they are explained elsewhere.
     HidePossibleValuesInHelp,
 
+    /// Like [`AppSettings::VersionSubcommand`], but the `version` subcommand is left out of the
+    /// `SUBCOMMANDS:` section of the help message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, AppSettings, SubCommand};
+    /// let help = App::new("myprog")
+    ///     .version("v1.1")
+    ///     .setting(AppSettings::HiddenVersionSubcommand)
+    ///     .subcommand(SubCommand::with_name("test"))
+    ///     .gen_help();
+    /// assert!(!help.lines().any(|l| l.trim_start().starts_with("version")));
+    /// ```
+    /// [`AppSettings::VersionSubcommand`]: ./enum.AppSettings.html#variant.VersionSubcommand
+    HiddenVersionSubcommand,
+
     /// Tries to match unknown args to partial [`subcommands`] or their [aliases]. For example to
     /// match a subcommand named `test`, one could use `t`, `te`, `tes`, and `test`.
     ///
@@ -700,6 +825,30 @@ pub enum AppSettings {
     /// ```
     NoBinaryName,
 
+    /// Treats `_` and `-` as the same character when matching long arguments, so that
+    /// `--foo_bar` and `--foo-bar` are interchangeable no matter which spelling was used in
+    /// [`Arg::long`] or [`Arg::alias`]. An exact match is always preferred.
+    ///
+    /// **NOTE:** This setting only applies to the current command, use
+    /// [`App::global_setting`] to also apply it to all child subcommands.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings};
+    /// let m = App::new("prog")
+    ///     .setting(AppSettings::NormalizeLongSeparators)
+    ///     .arg(Arg::with_name("dry").long("dry-run"))
+    ///     .get_matches_from(vec![
+    ///         "prog", "--dry_run"
+    ///     ]);
+    /// assert!(m.is_present("dry"));
+    /// ```
+    /// [`Arg::long`]: ./struct.Arg.html#method.long
+    /// [`Arg::alias`]: ./struct.Arg.html#method.alias
+    /// [`App::global_setting`]: ./struct.App.html#method.global_setting
+    NormalizeLongSeparators,
+
     /// Places the help string for all arguments on the line after the argument.
     ///
     /// # Examples
@@ -751,6 +900,28 @@ pub enum AppSettings {
     #[deprecated(since = "2.27.0", note = "No longer required to propagate values")]
     PropagateGlobalValuesDown,
 
+    /// Like [`AppSettings::ExpandDefaultEnvVars`] (which it implies), but referencing an
+    /// environment variable that isn't set is an error of kind [`ErrorKind::ValueValidation`]
+    /// whenever the default value is actually used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings, ErrorKind};
+    /// let res = App::new("myprog")
+    ///     .setting(AppSettings::RequireDefaultEnvVars)
+    ///     .arg(Arg::with_name("config")
+    ///         .long("config")
+    ///         .default_value("${MY_APP_SURELY_UNSET}/config.toml"))
+    ///     .get_matches_from_safe(vec![
+    ///         "myprog"
+    ///     ]);
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::ValueValidation);
+    /// ```
+    /// [`AppSettings::ExpandDefaultEnvVars`]: ./enum.AppSettings.html#variant.ExpandDefaultEnvVars
+    /// [`ErrorKind::ValueValidation`]: ./enum.ErrorKind.html#variant.ValueValidation
+    RequireDefaultEnvVars,
+
     /// Allows [`SubCommand`]s to override all requirements of the parent command.
     /// For example if you had a subcommand or top level application with a required argument
     /// that is only required as long as there is no subcommand present,
@@ -938,6 +1109,33 @@ pub enum AppSettings {
     /// [`SubCommand`]: ./struct.SubCommand.html
     VersionlessSubcommands,
 
+    /// Adds a `version` subcommand which prints the long version message, just like
+    /// `--version`, and exits. Unlike the `help` subcommand it is only added when this setting is
+    /// used. It is listed in the `SUBCOMMANDS:` section of the help message, see
+    /// [`AppSettings::HiddenVersionSubcommand`] for a version subcommand which isn't.
+    ///
+    /// **NOTE:** If a [`SubCommand`] named `version` is declared, it takes precedence and this
+    /// setting has no effect. It has no effect either when [`AppSettings::DisableVersion`] is
+    /// used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, AppSettings, ErrorKind};
+    /// let res = App::new("myprog")
+    ///     .version("v1.1")
+    ///     .setting(AppSettings::VersionSubcommand)
+    ///     .get_matches_from_safe(vec![
+    ///         "myprog", "version"
+    ///     ]);
+    /// assert!(res.is_err());
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::VersionDisplayed);
+    /// ```
+    /// [`AppSettings::HiddenVersionSubcommand`]: ./enum.AppSettings.html#variant.HiddenVersionSubcommand
+    /// [`AppSettings::DisableVersion`]: ./enum.AppSettings.html#variant.DisableVersion
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    VersionSubcommand,
+
     /// Will display a message "Press \[ENTER\]/\[RETURN\] to continue..." and wait for user before
     /// exiting
     ///
@@ -966,6 +1164,8 @@ pub enum AppSettings {
 
     #[doc(hidden)] NeedsSubcommandHelp,
 
+    #[doc(hidden)] NeedsSubcommandVersion,
+
     #[doc(hidden)] LowIndexMultiplePositional,
 
     #[doc(hidden)] TrailingValues,
@@ -1018,6 +1218,13 @@ impl FromStr for AppSettings {
             "validargfound" => Ok(AppSettings::ValidArgFound),
             "propagated" => Ok(AppSettings::Propagated),
             "trailingvalues" => Ok(AppSettings::TrailingValues),
+            "expanddefaultenvvars" => Ok(AppSettings::ExpandDefaultEnvVars),
+            "requiredefaultenvvars" => Ok(AppSettings::RequireDefaultEnvVars),
+            "caseinsensitivesubcommands" => Ok(AppSettings::CaseInsensitiveSubcommands),
+            "allowresponsefiles" => Ok(AppSettings::AllowResponseFiles),
+            "versionsubcommand" => Ok(AppSettings::VersionSubcommand),
+            "hiddenversionsubcommand" => Ok(AppSettings::HiddenVersionSubcommand),
+            "normalizelongseparators" => Ok(AppSettings::NormalizeLongSeparators),
             _ => Err("unknown AppSetting, cannot convert from str".to_owned()),
         }
     }
@@ -1169,6 +1376,34 @@ mod test {
             "infersubcommands".parse::<AppSettings>().unwrap(),
             AppSettings::InferSubcommands
         );
+        assert_eq!(
+            "expanddefaultenvvars".parse::<AppSettings>().unwrap(),
+            AppSettings::ExpandDefaultEnvVars
+        );
+        assert_eq!(
+            "requiredefaultenvvars".parse::<AppSettings>().unwrap(),
+            AppSettings::RequireDefaultEnvVars
+        );
+        assert_eq!(
+            "caseinsensitivesubcommands".parse::<AppSettings>().unwrap(),
+            AppSettings::CaseInsensitiveSubcommands
+        );
+        assert_eq!(
+            "allowresponsefiles".parse::<AppSettings>().unwrap(),
+            AppSettings::AllowResponseFiles
+        );
+        assert_eq!(
+            "versionsubcommand".parse::<AppSettings>().unwrap(),
+            AppSettings::VersionSubcommand
+        );
+        assert_eq!(
+            "hiddenversionsubcommand".parse::<AppSettings>().unwrap(),
+            AppSettings::HiddenVersionSubcommand
+        );
+        assert_eq!(
+            "normalizelongseparators".parse::<AppSettings>().unwrap(),
+            AppSettings::NormalizeLongSeparators
+        );
         assert!("hahahaha".parse::<AppSettings>().is_err());
     }
 }
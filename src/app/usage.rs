@@ -49,6 +49,8 @@ pub fn create_usage_no_title(p: &Parser, used: &[&str]) -> String {
     debugln!("usage::create_usage_no_title;");
     if let Some(u) = p.meta.usage_str {
         String::from(&*u)
+    } else if !p.meta.usage_lines.is_empty() {
+        p.meta.usage_lines.join("\n    ")
     } else if used.is_empty() {
         create_help_usage(p, true)
     } else {
@@ -453,12 +455,11 @@ pub fn get_required_usage_from<'a, 'b>(
         debugln!("usage::get_required_usage_from:iter:{}:", a);
         let arg = find_by_name!(p, *a, flags, iter)
             .map(|f| f.to_string())
-            .unwrap_or_else(|| {
-                find_by_name!(p, *a, opts, iter)
-                    .map(|o| o.to_string())
-                    .expect(INTERNAL_ERROR_MSG)
-            });
-        ret_val.push_back(arg);
+            .or_else(|| find_by_name!(p, *a, opts, iter).map(|o| o.to_string()));
+        // Global args may require args which only exist in a subcommand
+        if let Some(arg) = arg {
+            ret_val.push_back(arg);
+        }
     }
     let mut g_vec: Vec<String> = vec![];
     for g in desc_reqs
@@ -75,11 +75,83 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
             self.validate_required(matcher)?;
         }
         self.validate_matched_args(matcher)?;
+        self.normalize_case_insensitive_vals(matcher);
+        self.add_group_members(matcher);
+        self.add_invalid_value_messages(matcher);
         matcher.usage(usage::create_usage_with_title(self.0, &[]));
+        self.run_validators(matcher)
+    }
 
+    fn run_validators(&self, matcher: &ArgMatcher<'a>) -> ClapResult<()> {
+        debugln!("Validator::run_validators;");
+        for v in &self.0.meta.validators {
+            if let Err(e) = v(&matcher.0) {
+                return Err(Error::custom_validation(
+                    e,
+                    &*usage::create_error_usage(self.0, matcher, None),
+                    self.0.color(),
+                ));
+            }
+        }
         Ok(())
     }
 
+    // Replaces values matched case insensitively with the possible value as it was declared
+    fn normalize_case_insensitive_vals(&self, matcher: &mut ArgMatcher<'a>) {
+        debugln!("Validator::normalize_case_insensitive_vals;");
+        let opts = self.0.opts.iter().map(|o| (o.b.name, &o.b.settings, &o.v.possible_vals));
+        let pos = self.0
+            .positionals
+            .values()
+            .map(|p| (p.b.name, &p.b.settings, &p.v.possible_vals));
+        for (name, settings, p_vals) in opts.chain(pos) {
+            if !settings.is_set(ArgSettings::CaseInsensitive) {
+                continue;
+            }
+            if let (Some(p_vals), Some(ma)) = (p_vals.as_ref(), matcher.get_mut(name)) {
+                for val in &mut ma.vals {
+                    let declared = val.to_str()
+                        .and_then(|v| p_vals.iter().find(|pv| pv.eq_ignore_ascii_case(v)));
+                    if let Some(pv) = declared {
+                        *val = (*pv).into();
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_group_members(&self, matcher: &mut ArgMatcher<'a>) {
+        debugln!("Validator::add_group_members;");
+        for g in &self.0.groups {
+            let first_used = self.0
+                .arg_names_in_group(g.name)
+                .into_iter()
+                .filter(|n| matcher.contains(n))
+                .min_by_key(|n| {
+                    matcher
+                        .get(n)
+                        .and_then(|ma| ma.indices.first().cloned())
+                        .unwrap_or(usize::MAX)
+                });
+            if let Some(n) = first_used {
+                matcher.0.groups.insert(g.name, n);
+            }
+        }
+    }
+
+    fn add_invalid_value_messages(&self, matcher: &mut ArgMatcher<'a>) {
+        debugln!("Validator::add_invalid_value_messages;");
+        let opts = self.0.opts.iter().map(|o| (o.b.name, o.v.invalid_msg));
+        let pos = self.0.positionals.values().map(|p| (p.b.name, p.v.invalid_msg));
+        for (name, msg) in opts.chain(pos) {
+            if let Some(msg) = msg {
+                if matcher.contains(name) {
+                    matcher.0.invalid_msgs.insert(name, msg.to_owned());
+                }
+            }
+        }
+    }
+
     fn validate_arg_values<A>(
         &self,
         arg: &A,
@@ -90,6 +162,8 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
         A: AnyArg<'a, 'b> + Display,
     {
         debugln!("Validator::validate_arg_values: arg={:?}", arg.name());
+        // Values which can't be used at all are an error of their own, so they're checked before
+        // any invalid values are collected
         for val in &ma.vals {
             if self.0.is_set(AS::StrictUtf8) && val.to_str().is_none() {
                 debugln!(
@@ -101,6 +175,21 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                     self.0.color(),
                 ));
             }
+            if !arg.is_set(ArgSettings::EmptyValues) && val.is_empty()
+                && matcher.contains(&*arg.name())
+            {
+                debugln!("Validator::validate_arg_values: illegal empty val found");
+                return Err(Error::empty_value(
+                    arg,
+                    &*usage::create_error_usage(self.0, matcher, None),
+                    self.0.color(),
+                ));
+            }
+        }
+        // Invalid values are collected so they can all be reported at once
+        let mut bad_vals = vec![];
+        let mut vtor_errs = vec![];
+        for val in &ma.vals {
             if let Some(p_vals) = arg.possible_vals() {
                 debugln!("Validator::validate_arg_values: possible_vals={:?}", p_vals);
                 let val_str = val.to_string_lossy();
@@ -110,30 +199,16 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                     p_vals.contains(&&*val_str)
                 };
                 if !ok {
-                    return Err(Error::invalid_value(
-                        val_str,
-                        p_vals,
-                        arg,
-                        &*usage::create_error_usage(self.0, matcher, None),
-                        self.0.color(),
-                    ));
+                    bad_vals.push(val_str.into_owned());
+                    continue;
                 }
             }
-            if !arg.is_set(ArgSettings::EmptyValues) && val.is_empty()
-                && matcher.contains(&*arg.name())
-            {
-                debugln!("Validator::validate_arg_values: illegal empty val found");
-                return Err(Error::empty_value(
-                    arg,
-                    &*usage::create_error_usage(self.0, matcher, None),
-                    self.0.color(),
-                ));
-            }
             if let Some(vtor) = arg.validator() {
                 debug!("Validator::validate_arg_values: checking validator...");
                 if let Err(e) = vtor(val.to_string_lossy().into_owned()) {
                     sdebugln!("error");
-                    return Err(Error::value_validation(Some(arg), e, self.0.color()));
+                    vtor_errs.push(e);
+                    continue;
                 } else {
                     sdebugln!("good");
                 }
@@ -142,16 +217,36 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                 debug!("Validator::validate_arg_values: checking validator_os...");
                 if let Err(e) = vtor(val) {
                     sdebugln!("error");
-                    return Err(Error::value_validation(
-                        Some(arg),
-                        (*e).to_string_lossy().to_string(),
-                        self.0.color(),
-                    ));
+                    vtor_errs.push((*e).to_string_lossy().to_string());
                 } else {
                     sdebugln!("good");
                 }
             }
         }
+        if bad_vals.len() == 1 {
+            return Err(Error::invalid_value(
+                &*bad_vals[0],
+                arg.possible_vals().expect(INTERNAL_ERROR_MSG),
+                arg,
+                &*usage::create_error_usage(self.0, matcher, None),
+                self.0.color(),
+            ));
+        } else if !bad_vals.is_empty() {
+            return Err(Error::invalid_values(
+                &*bad_vals,
+                arg.possible_vals().expect(INTERNAL_ERROR_MSG),
+                arg,
+                &*usage::create_error_usage(self.0, matcher, None),
+                self.0.color(),
+            ));
+        }
+        if !vtor_errs.is_empty() {
+            return Err(Error::value_validation(
+                Some(arg),
+                vtor_errs.join("; "),
+                self.0.color(),
+            ));
+        }
         Ok(())
     }
 
@@ -271,6 +366,30 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
         Ok(())
     }
 
+    // Checks the conflicts and requirements of global args that were used at a parent level
+    pub fn validate_inherited(
+        &mut self,
+        names: &[&'a str],
+        matcher: &mut ArgMatcher<'a>,
+    ) -> ClapResult<()> {
+        debugln!("Validator::validate_inherited: names={:?}", names);
+        if names.is_empty() {
+            return Ok(());
+        }
+        self.validate_blacklist(matcher)?;
+        for name in names {
+            let ma = matcher.get(name).expect(INTERNAL_ERROR_MSG);
+            if let Some(opt) = find_by_name!(self.0, *name, opts, iter) {
+                self.validate_arg_requires(opt, ma, matcher)?;
+            } else if let Some(flag) = find_by_name!(self.0, *name, flags, iter) {
+                self.validate_arg_requires(flag, ma, matcher)?;
+            } else if let Some(pos) = find_by_name!(self.0, *name, positionals, values) {
+                self.validate_arg_requires(pos, ma, matcher)?;
+            }
+        }
+        Ok(())
+    }
+
     fn validate_matched_args(&self, matcher: &mut ArgMatcher<'a>) -> ClapResult<()> {
         debugln!("Validator::validate_matched_args;");
         for (name, ma) in matcher.iter() {
@@ -424,14 +543,19 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
     {
         debugln!("Validator::validate_arg_requires:{};", a.name());
         if let Some(a_reqs) = a.requires() {
-            for &(val, name) in a_reqs.iter().filter(|&&(val, _)| val.is_some()) {
-                let missing_req =
-                    |v| v == val.expect(INTERNAL_ERROR_MSG) && !matcher.contains(name);
-                if ma.vals.iter().any(missing_req) {
-                    return self.missing_required_error(matcher, None);
+            // A global arg may require args that only exist in subcommands, those are checked
+            // once the arg reaches them
+            let a_reqs = a_reqs
+                .iter()
+                .filter(|&&(_, name)| !a.is_set(ArgSettings::Global) || self.knows(name))
+                .collect::<Vec<_>>();
+            for &&(val, name) in a_reqs.iter().filter(|&&&(val, _)| val.is_some()) {
+                let val = val.expect(INTERNAL_ERROR_MSG);
+                if ma.vals.iter().any(|v| v == val) && !matcher.contains(name) {
+                    return self.missing_required_if_error(matcher, a, val, name);
                 }
             }
-            for &(_, name) in a_reqs.iter().filter(|&&(val, _)| val.is_none()) {
+            for &&(_, name) in a_reqs.iter().filter(|&&&(val, _)| val.is_none()) {
                 if !matcher.contains(name) {
                     return self.missing_required_error(matcher, Some(name));
                 }
@@ -440,6 +564,10 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
         Ok(())
     }
 
+    fn knows(&self, name: &str) -> bool {
+        find_any_by_name!(self.0, name).is_some() || self.0.groups.iter().any(|g| g.name == name)
+    }
+
     fn validate_required(&mut self, matcher: &ArgMatcher) -> ClapResult<()> {
         debugln!(
             "Validator::validate_required: required={:?};",
@@ -489,6 +617,11 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
         for &(a, v, r) in &self.0.r_ifs {
             if let Some(ma) = matcher.get(a) {
                 if matcher.get(r).is_none() && ma.vals.iter().any(|val| val == v) {
+                    if let Some(opt) = find_by_name!(self.0, a, opts, iter) {
+                        return self.missing_required_if_error(matcher, opt, v, r);
+                    } else if let Some(pos) = find_by_name!(self.0, a, positionals, values) {
+                        return self.missing_required_if_error(matcher, pos, v, r);
+                    }
                     return self.missing_required_error(matcher, Some(r));
                 }
             }
@@ -536,10 +669,14 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
 
     fn missing_required_error(&self, matcher: &ArgMatcher, extra: Option<&str>) -> ClapResult<()> {
         debugln!("Validator::missing_required_error: extra={:?}", extra);
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: self.0.color(),
-        });
+        let color = self.0.color();
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
         let mut reqs = self.0.required.iter().map(|&r| &*r).collect::<Vec<_>>();
         if let Some(r) = extra {
             reqs.push(r);
@@ -564,6 +701,50 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
         ))
     }
 
+    fn missing_required_if_error<A>(
+        &self,
+        matcher: &ArgMatcher,
+        a: &A,
+        val: &str,
+        name: &'a str,
+    ) -> ClapResult<()>
+    where
+        A: AnyArg<'a, 'b> + Display,
+    {
+        debugln!(
+            "Validator::missing_required_if_error: a={}, val={}, name={}",
+            a.name(),
+            val,
+            name
+        );
+        let color = self.0.color();
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
+        let req_args = usage::get_required_usage_from(self.0, &[name], Some(matcher), None, true)
+            .iter()
+            .fold(String::new(), |acc, s| {
+                acc + &format!("\n    {}", c.error(s))[..]
+            });
+        let because = format!(
+            "{}\n\nrequired because '{}' was used with the value '{}'",
+            req_args,
+            c.warning(a.to_string()),
+            c.warning(val)
+        );
+        let mut e = Error::missing_required_argument(
+            &*because,
+            &*usage::create_error_usage(self.0, matcher, Some(name)),
+            self.0.color(),
+        );
+        e.info = Some(vec![name.to_owned(), a.name().to_owned(), val.to_owned()]);
+        Err(e)
+    }
+
     #[inline]
     fn is_missing_required_ok(&self, a: &AnyArg, matcher: &ArgMatcher) -> bool {
         debugln!("Validator::is_missing_required_ok: a={}", a.name());
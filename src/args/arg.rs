@@ -1,5 +1,6 @@
 #[cfg(feature = "yaml")]
 use std::collections::BTreeMap;
+use std::env;
 use std::rc::Rc;
 use std::ffi::{OsStr, OsString};
 #[cfg(any(target_os = "windows", target_arch = "wasm32"))]
@@ -112,6 +113,8 @@ impl<'a, 'b> Arg<'a, 'b> {
                 "global" => yaml_to_bool!(a, v, global),
                 "multiple" => yaml_to_bool!(a, v, multiple),
                 "hidden" => yaml_to_bool!(a, v, hidden),
+                "negatable" => yaml_to_bool!(a, v, negatable),
+                "count" => yaml_to_bool!(a, v, count),
                 "next_line_help" => yaml_to_bool!(a, v, next_line_help),
                 "empty_values" => yaml_to_bool!(a, v, empty_values),
                 "group" => yaml_to_str!(a, v, group),
@@ -288,7 +291,13 @@ impl<'a, 'b> Arg<'a, 'b> {
     /// [`Arg::from_usage`]: ./struct.Arg.html#method.from_usage
     pub fn from_usage(u: &'a str) -> Self {
         let parser = UsageParser::from_usage(u);
-        parser.parse()
+        let arg = parser.parse();
+        debug_assert!(
+            !arg.b.name.is_empty(),
+            "No name found for Arg when parsing usage string: {}",
+            u
+        );
+        arg
     }
 
     /// Sets the short version of the argument without the preceding `-`.
@@ -332,6 +341,36 @@ impl<'a, 'b> Arg<'a, 'b> {
         self
     }
 
+    /// Sets several short versions of the argument at once, all of which behave exactly the same
+    /// at runtime. The first one is the primary [`short`], which is the only one displayed in the
+    /// help message and usage strings.
+    ///
+    /// **NOTE:** Unlike [`Arg::short`] the characters are used as is, and an empty slice leaves
+    /// the argument without any [`short`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let app = || App::new("prog")
+    ///     .arg(Arg::with_name("info")
+    ///         .shorts(&['i', '?']));
+    ///
+    /// assert!(app().get_matches_from(vec!["prog", "-i"]).is_present("info"));
+    /// assert!(app().get_matches_from(vec!["prog", "-?"]).is_present("info"));
+    /// ```
+    /// [`short`]: ./struct.Arg.html#method.short
+    /// [`Arg::short`]: ./struct.Arg.html#method.short
+    pub fn shorts(mut self, shorts: &[char]) -> Self {
+        self.s.short = shorts.first().cloned();
+        self.s.shorts = if shorts.len() > 1 {
+            Some(shorts[1..].to_vec())
+        } else {
+            None
+        };
+        self
+    }
+
     /// Sets the long version of the argument without the preceding `--`.
     ///
     /// By default `clap` automatically assigns `version` and `help` to the auto-generated
@@ -1541,6 +1580,52 @@ impl<'a, 'b> Arg<'a, 'b> {
         self
     }
 
+    /// Allows a conditional requirement which applies to a whole set of values. The requirement
+    /// will only become valid if this arg's value equals *any* of `vals`. This is the same as
+    /// calling [`Arg::requires_if`] once for each value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::Arg;
+    /// Arg::with_name("format")
+    ///     .requires_if_eq_any(&["json", "yaml"], "schema")
+    /// # ;
+    /// ```
+    ///
+    /// Setting [`Arg::requires_if_eq_any(&["json", "yaml"], "schema")`] requires that `schema` be
+    /// used at runtime if the defining argument's value is either `json` or `yaml`. If the defining
+    /// argument's value is anything else, `schema` isn't required.
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let res = App::new("prog")
+    ///     .arg(Arg::with_name("format")
+    ///         .long("format")
+    ///         .takes_value(true)
+    ///         .requires_if_eq_any(&["json", "yaml"], "schema"))
+    ///     .arg(Arg::with_name("schema")
+    ///         .long("schema")
+    ///         .takes_value(true))
+    ///     .get_matches_from_safe(vec![
+    ///         "prog", "--format", "yaml"
+    ///     ]);
+    ///
+    /// assert!(res.is_err()); // We used --format=yaml so --schema <schema> is required
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+    /// ```
+    /// [`Arg::requires_if`]: ./struct.Arg.html#method.requires_if
+    /// [`Arg::requires_if_eq_any(&["json", "yaml"], "schema")`]: ./struct.Arg.html#method.requires_if_eq_any
+    pub fn requires_if_eq_any(mut self, vals: &[&'b str], arg: &'a str) -> Self {
+        {
+            let vec = self.b.requires.get_or_insert_with(Vec::new);
+            for &val in vals {
+                vec.push((Some(val), arg));
+            }
+        }
+        self
+    }
+
     /// Allows specifying that an argument is [required] conditionally. The requirement will only
     /// become valid if the specified `arg`'s value equals `val`.
     ///
@@ -2348,6 +2433,75 @@ impl<'a, 'b> Arg<'a, 'b> {
         }
     }
 
+    /// Allows a flag with a [`long`] version to be explicitly turned off, either with
+    /// `--no-<long>` or with `--<long>=false` (`--<long>=true` is also accepted). Whichever is
+    /// used last wins, and a flag which was turned off is no longer [present].
+    ///
+    /// Use [`ArgMatches::flag_state`] to tell an explicitly disabled flag apart from one which
+    /// wasn't used at all.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let app = || App::new("prog")
+    ///     .arg(Arg::with_name("color")
+    ///         .long("color")
+    ///         .negatable(true));
+    ///
+    /// let m = app().get_matches_from(vec!["prog", "--color", "--no-color"]);
+    /// assert!(!m.is_present("color"));
+    /// assert_eq!(m.flag_state("color"), Some(false));
+    ///
+    /// let m = app().get_matches_from(vec!["prog", "--color=false", "--color=true"]);
+    /// assert_eq!(m.flag_state("color"), Some(true));
+    ///
+    /// let m = app().get_matches_from(vec!["prog"]);
+    /// assert_eq!(m.flag_state("color"), None);
+    /// ```
+    /// [`long`]: ./struct.Arg.html#method.long
+    /// [present]: ./struct.ArgMatches.html#method.is_present
+    /// [`ArgMatches::flag_state`]: ./struct.ArgMatches.html#method.flag_state
+    pub fn negatable(self, n: bool) -> Self {
+        if n {
+            self.set(ArgSettings::Negatable)
+        } else {
+            self.unset(ArgSettings::Negatable)
+        }
+    }
+
+    /// Declares a flag as a counter, such as `-v`, `-vv` and `-vvv` for increasing levels of
+    /// verbosity. This implies [`Arg::multiple(true)`], and the number of uses is read back with
+    /// [`ArgMatches::count_of`].
+    ///
+    /// **NOTE:** Counters are flags, so this can't be combined with [`Arg::takes_value(true)`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("verbose")
+    ///         .short("v")
+    ///         .long("verbose")
+    ///         .count(true))
+    ///     .get_matches_from(vec![
+    ///         "prog", "-vv", "--verbose"
+    ///     ]);
+    ///
+    /// assert_eq!(m.count_of("verbose"), 3);
+    /// ```
+    /// [`Arg::multiple(true)`]: ./struct.Arg.html#method.multiple
+    /// [`Arg::takes_value(true)`]: ./struct.Arg.html#method.takes_value
+    /// [`ArgMatches::count_of`]: ./struct.ArgMatches.html#method.count_of
+    pub fn count(self, c: bool) -> Self {
+        if c {
+            self.set(ArgSettings::Count).set(ArgSettings::Multiple)
+        } else {
+            self.unset(ArgSettings::Count)
+        }
+    }
+
     /// Specifies a list of possible values for this argument. At runtime, `clap` verifies that
     /// only one of the specified values was used, or fails with an error message.
     ///
@@ -2469,7 +2623,8 @@ impl<'a, 'b> Arg<'a, 'b> {
     }
 
     /// When used with [`Arg::possible_values`] it allows the argument value to pass validation even if
-    /// the case differs from that of the specified `possible_value`.
+    /// the case differs from that of the specified `possible_value`. The value is stored in the
+    /// [`ArgMatches`] using the casing of the matching `possible_value`.
     ///
     /// **Pro Tip:** Use this setting with [`arg_enum!`]
     ///
@@ -2477,7 +2632,6 @@ impl<'a, 'b> Arg<'a, 'b> {
     ///
     /// ```rust
     /// # use clap::{App, Arg};
-    /// # use std::ascii::AsciiExt;
     /// let m = App::new("pv")
     ///     .arg(Arg::with_name("option")
     ///         .long("--option")
@@ -2488,7 +2642,7 @@ impl<'a, 'b> Arg<'a, 'b> {
     ///         "pv", "--option", "TeSt123",
     ///     ]);
     ///
-    /// assert!(m.value_of("option").unwrap().eq_ignore_ascii_case("test123"));
+    /// assert_eq!(m.value_of("option"), Some("test123"));
     /// ```
     ///
     /// This setting also works when multiple values can be defined:
@@ -2509,8 +2663,9 @@ impl<'a, 'b> Arg<'a, 'b> {
     ///     ]);
     ///
     /// let matched_vals = m.values_of("option").unwrap().collect::<Vec<_>>();
-    /// assert_eq!(&*matched_vals, &["TeSt123", "teST123", "tESt321"]);
+    /// assert_eq!(&*matched_vals, &["test123", "test123", "test321"]);
     /// ```
+    /// [`ArgMatches`]: ./struct.ArgMatches.html
     /// [`Arg::case_insensitive(true)`]: ./struct.Arg.html#method.possible_values
     /// [`arg_enum!`]: ./macro.arg_enum.html
     pub fn case_insensitive(self, ci: bool) -> Self {
@@ -2658,6 +2813,9 @@ impl<'a, 'b> Arg<'a, 'b> {
     /// to to be passed to the closure. This performance hit is extremely minimal in the grand
     /// scheme of things.
     ///
+    /// **NOTE:** The closure is called once for *each* value, so it may be called several times
+    /// for arguments which accept [multiple values] or are used more than once.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -2680,6 +2838,7 @@ impl<'a, 'b> Arg<'a, 'b> {
     /// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
     /// [`Err(String)`]: https://doc.rust-lang.org/std/result/enum.Result.html#variant.Err
     /// [`Rc`]: https://doc.rust-lang.org/std/rc/struct.Rc.html
+    /// [multiple values]: ./struct.Arg.html#method.multiple
     pub fn validator<F>(mut self, f: F) -> Self
     where
         F: Fn(String) -> Result<(), String> + 'static,
@@ -2726,6 +2885,34 @@ impl<'a, 'b> Arg<'a, 'b> {
         self
     }
 
+    /// Sets the error message used when this argument's value can't be converted into the
+    /// requested type by [`ArgMatches::value_of_t`] (and therefore [`value_t!`]), instead of the
+    /// generic "isn't a valid value" message. Any `{}` in the message is replaced with the value
+    /// the user provided.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("port")
+    ///         .long("port")
+    ///         .takes_value(true)
+    ///         .invalid_value_message("port must be between 1 and 65535, not '{}'"))
+    ///     .get_matches_from(vec![
+    ///         "prog", "--port", "70000"
+    ///     ]);
+    ///
+    /// let err = m.value_of_t::<u16>("port").unwrap_err();
+    /// assert!(err.message.contains("port must be between 1 and 65535, not '70000'"));
+    /// ```
+    /// [`ArgMatches::value_of_t`]: ./struct.ArgMatches.html#method.value_of_t
+    /// [`value_t!`]: ./macro.value_t!.html
+    pub fn invalid_value_message(mut self, msg: &'b str) -> Self {
+        self.v.invalid_msg = Some(msg);
+        self
+    }
+
     /// Specifies the *maximum* number of values are for this argument. For example, if you had a
     /// `-f <file>` argument where you wanted up to 3 'files' you would set `.max_values(3)`, and
     /// this argument would be satisfied if the user provided, 1, 2, or 3 values.
@@ -2765,17 +2952,38 @@ impl<'a, 'b> Arg<'a, 'b> {
     /// assert_eq!(files, ["file1", "file2"]);
     /// ```
     ///
-    /// Supplying more than the maximum number of values is an error
+    /// Once the maximum has been reached no further values are consumed, so any that follow are
+    /// parsed as positional arguments
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("file")
+    ///         .takes_value(true)
+    ///         .max_values(2)
+    ///         .short("F"))
+    ///     .arg(Arg::with_name("out"))
+    ///     .get_matches_from(vec![
+    ///         "prog", "-F", "file1", "file2", "file3"
+    ///     ]);
+    ///
+    /// let files: Vec<_> = m.values_of("file").unwrap().collect();
+    /// assert_eq!(files, ["file1", "file2"]);
+    /// assert_eq!(m.value_of("out"), Some("file3"));
+    /// ```
+    ///
+    /// Supplying more than the maximum number of values over several occurrences is an error
     ///
     /// ```rust
     /// # use clap::{App, Arg, ErrorKind};
     /// let res = App::new("prog")
     ///     .arg(Arg::with_name("file")
     ///         .takes_value(true)
+    ///         .multiple(true)
     ///         .max_values(2)
     ///         .short("F"))
     ///     .get_matches_from_safe(vec![
-    ///         "prog", "-F", "file1", "file2", "file3"
+    ///         "prog", "-F", "file1", "-F", "file2", "-F", "file3"
     ///     ]);
     ///
     /// assert!(res.is_err());
@@ -3586,19 +3794,19 @@ impl<'a, 'b> Arg<'a, 'b> {
     /// [`Arg::takes_value(true)`]: ./struct.Arg.html#method.takes_value
     /// [`Arg::multiple(true)`]: ./struct.Arg.html#method.multiple
     /// [`Arg::use_delimiter(true)`]: ./struct.Arg.html#method.use_delimiter
-    // pub fn env(self, name: &'a str) -> Self {
-    //     self.env_os(OsStr::new(name))
-    // }
+    pub fn env(self, name: &'a str) -> Self {
+        self.env_os(OsStr::new(name))
+    }
 
     /// Specifies that if the value is not passed in as an argument, that it should be retrieved
     /// from the environment if available in the exact same manner as [`Arg::env`] only using
     /// [`OsStr`]s instead.
-    // pub fn env_os(mut self, name: &'a OsStr) -> Self {
-    //     self.setb(ArgSettings::TakesValue);
+    pub fn env_os(mut self, name: &'a OsStr) -> Self {
+        self.setb(ArgSettings::TakesValue);
 
-    //     self.v.env = Some((name, env::var_os(name)));
-    //     self
-    // }
+        self.v.env = Some((name, env::var_os(name)));
+        self
+    }
 
     /// @TODO @p2 @docs @release: write docs
     pub fn hide_env_values(self, hide: bool) -> Self {
@@ -3723,6 +3931,49 @@ impl<'a, 'b> Arg<'a, 'b> {
         self
     }
 
+    /// Lists this argument under its own `heading` section in the help message, instead of the
+    /// default `FLAGS`, `OPTIONS` or `ARGS` sections. Arguments sharing a heading are grouped
+    /// together, and the custom sections are printed after the default ones in the order their
+    /// headings were first used. Within a section, flags and options keep the order they were
+    /// declared in (unless they have a [`Arg::display_order`]), followed by any positional
+    /// arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("world")
+    ///         .long("world")
+    ///         .takes_value(true)
+    ///         .help_heading("WORLD OPTIONS")
+    ///         .help("The world to join"))
+    ///     .get_matches_from(vec![
+    ///         "prog", "--help"
+    ///     ]);
+    /// ```
+    ///
+    /// The above example displays the following help message
+    ///
+    /// ```notrust
+    /// prog
+    ///
+    /// USAGE:
+    ///     prog [FLAGS] [OPTIONS]
+    ///
+    /// FLAGS:
+    ///     -h, --help       Prints help information
+    ///     -V, --version    Prints version information
+    ///
+    /// WORLD OPTIONS:
+    ///         --world <world>    The world to join
+    /// ```
+    /// [`Arg::display_order`]: ./struct.Arg.html#method.display_order
+    pub fn help_heading(mut self, heading: &'b str) -> Self {
+        self.b.help_heading = Some(heading);
+        self
+    }
+
     /// Indicates that all parameters passed after this should not be parsed
     /// individually, but rather passed in their entirety. It is worth noting
     /// that setting this requires all values to come after a `--` to indicate they
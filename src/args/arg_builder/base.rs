@@ -8,6 +8,7 @@ where
     pub name: &'a str,
     pub help: Option<&'b str>,
     pub long_help: Option<&'b str>,
+    pub help_heading: Option<&'b str>,
     pub blacklist: Option<Vec<&'a str>>,
     pub settings: ArgFlags,
     pub r_unless: Option<Vec<&'a str>>,
@@ -3,6 +3,7 @@ use Arg;
 #[derive(Debug)]
 pub struct Switched<'b> {
     pub short: Option<char>,
+    pub shorts: Option<Vec<char>>, // any shorts besides the primary one
     pub long: Option<&'b str>,
     pub aliases: Option<Vec<(&'b str, bool)>>, // (name, visible)
     pub disp_ord: usize,
@@ -13,6 +14,7 @@ impl<'e> Default for Switched<'e> {
     fn default() -> Self {
         Switched {
             short: None,
+            shorts: None,
             long: None,
             aliases: None,
             disp_ord: 999,
@@ -29,6 +31,7 @@ impl<'e> Clone for Switched<'e> {
     fn clone(&self) -> Self {
         Switched {
             short: self.short,
+            shorts: self.shorts.clone(),
             long: self.long,
             aliases: self.aliases.clone(),
             disp_ord: self.disp_ord,
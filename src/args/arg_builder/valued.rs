@@ -23,6 +23,7 @@ where
     pub default_vals_ifs: Option<VecMap<(&'a str, Option<&'b OsStr>, &'b OsStr)>>,
     pub env: Option<(&'a OsStr, Option<OsString>)>,
     pub terminator: Option<&'b str>,
+    pub invalid_msg: Option<&'b str>,
 }
 
 impl<'n, 'e> Default for Valued<'n, 'e> {
@@ -40,6 +41,7 @@ impl<'n, 'e> Default for Valued<'n, 'e> {
             default_vals_ifs: None,
             env: None,
             terminator: None,
+            invalid_msg: None,
         }
     }
 }
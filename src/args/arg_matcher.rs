@@ -6,21 +6,40 @@ use std::ops::Deref;
 use std::mem;
 
 // Internal
-use args::{ArgMatches, MatchedArg, SubCommand};
+use args::{ArgMatches, MatchedArg, SubCommand, TokenKind};
 use args::AnyArg;
 use args::settings::ArgSettings;
 
+// The second field holds the role of each token parsed so far, but only when they're being
+// recorded for `App::get_matches_with_spans`
 #[doc(hidden)]
 #[allow(missing_debug_implementations)]
-pub struct ArgMatcher<'a>(pub ArgMatches<'a>);
+pub struct ArgMatcher<'a>(pub ArgMatches<'a>, Option<Vec<Option<TokenKind>>>);
 
 impl<'a> Default for ArgMatcher<'a> {
-    fn default() -> Self { ArgMatcher(ArgMatches::default()) }
+    fn default() -> Self { ArgMatcher(ArgMatches::default(), None) }
+}
+
+impl<'a> From<ArgMatches<'a>> for ArgMatcher<'a> {
+    fn from(matches: ArgMatches<'a>) -> Self { ArgMatcher(matches, None) }
 }
 
 impl<'a> ArgMatcher<'a> {
     pub fn new() -> Self { ArgMatcher::default() }
 
+    pub fn with_tokens() -> Self { ArgMatcher(ArgMatches::default(), Some(Vec::new())) }
+
+    // A matcher for a subcommand's args, which records tokens if this one does
+    pub fn for_subcommand(&self) -> Self {
+        if self.records_tokens() {
+            ArgMatcher::with_tokens()
+        } else {
+            ArgMatcher::new()
+        }
+    }
+
+    pub fn records_tokens(&self) -> bool { self.1.is_some() }
+
     pub fn process_arg_overrides<'b>(&mut self, a: Option<&AnyArg<'a, 'b>>, overrides: &mut Vec<(&'b str, &'a str)>, required: &mut Vec<&'a str>, check_all: bool) {
         debugln!("ArgMatcher::process_arg_overrides:{:?};", a.map_or(None, |a| Some(a.name())));
         if let Some(aa) = a {
@@ -112,7 +131,7 @@ impl<'a> ArgMatcher<'a> {
             }
         }
         if let Some(ref mut sc) = self.0.subcommand {
-            let mut am = ArgMatcher(mem::replace(&mut sc.matches, ArgMatches::new()));
+            let mut am = ArgMatcher::from(mem::replace(&mut sc.matches, ArgMatches::new()));
             am.fill_in_global_values(global_arg_vec, vals_map);
             mem::swap(&mut am.0, &mut sc.matches);
         }
@@ -152,8 +171,37 @@ impl<'a> ArgMatcher<'a> {
 
     pub fn iter(&self) -> Iter<&str, MatchedArg> { self.0.args.iter() }
 
+    pub fn negate(&mut self, arg: &'a str) {
+        debugln!("ArgMatcher::negate: arg={}", arg);
+        self.0.args.remove(arg);
+        if !self.0.negated.contains(&arg) {
+            self.0.negated.push(arg);
+        }
+    }
+
+    pub fn token(&mut self, kind: Option<TokenKind>) {
+        if let Some(ref mut tokens) = self.1 {
+            debugln!("ArgMatcher::token: kind={:?}", kind);
+            tokens.push(kind);
+        }
+    }
+
+    pub fn take_tokens(&mut self, other: &mut ArgMatcher) {
+        if let (Some(tokens), Some(others)) = (self.1.as_mut(), other.1.as_mut()) {
+            tokens.extend(others.drain(..));
+        }
+    }
+
+    pub fn tokens(&self) -> &[Option<TokenKind>] {
+        match self.1 {
+            Some(ref tokens) => tokens,
+            None => &[],
+        }
+    }
+
     pub fn inc_occurrence_of(&mut self, arg: &'a str) {
         debugln!("ArgMatcher::inc_occurrence_of: arg={}", arg);
+        self.0.negated.retain(|&n| n != arg);
         if let Some(a) = self.get_mut(arg) {
             a.occurs += 1;
             return;
@@ -174,6 +222,7 @@ impl<'a> ArgMatcher<'a> {
             occurs: 0,
             indices: Vec::with_capacity(1),
             vals: Vec::with_capacity(1),
+            ..MatchedArg::new()
         });
         ma.vals.push(val.to_owned());
     }
@@ -182,7 +231,7 @@ impl<'a> ArgMatcher<'a> {
         let ma = self.entry(arg).or_insert(MatchedArg {
             occurs: 0,
             indices: Vec::with_capacity(1),
-            vals: Vec::new(),
+            ..MatchedArg::new()
         });
         ma.indices.push(idx);
     }
@@ -202,7 +251,7 @@ impl<'a> ArgMatcher<'a> {
                 };
             } else if let Some(num) = o.max_vals() {
                 debugln!("ArgMatcher::needs_more_vals: max_vals...{}", num);
-                return !((ma.vals.len() as u64) > num);
+                return (ma.vals.len() as u64) < num;
             } else if o.min_vals().is_some() {
                 debugln!("ArgMatcher::needs_more_vals: min_vals...true");
                 return true;
@@ -2,13 +2,16 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::ffi::{OsStr, OsString};
+use std::fmt;
 use std::iter::Map;
 use std::slice::Iter;
+use std::str::FromStr;
 
 // Internal
 use INVALID_UTF8;
-use args::MatchedArg;
+use args::{ArgKind, MatchedArg};
 use args::SubCommand;
+use errors::Error;
 
 /// Used to get information about the arguments that where supplied to the program at runtime by
 /// the user. New instances of this struct are obtained by using the [`App::get_matches`] family of
@@ -57,11 +60,14 @@ use args::SubCommand;
 /// }
 /// ```
 /// [`App::get_matches`]: ./struct.App.html#method.get_matches
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ArgMatches<'a> {
     #[doc(hidden)] pub args: HashMap<&'a str, MatchedArg>,
     #[doc(hidden)] pub subcommand: Option<Box<SubCommand<'a>>>,
     #[doc(hidden)] pub usage: Option<String>,
+    #[doc(hidden)] pub groups: HashMap<&'a str, &'a str>,
+    #[doc(hidden)] pub invalid_msgs: HashMap<&'a str, String>,
+    #[doc(hidden)] pub negated: Vec<&'a str>,
 }
 
 impl<'a> Default for ArgMatches<'a> {
@@ -70,6 +76,9 @@ impl<'a> Default for ArgMatches<'a> {
             args: HashMap::new(),
             subcommand: None,
             usage: None,
+            groups: HashMap::new(),
+            invalid_msgs: HashMap::new(),
+            negated: Vec::new(),
         }
     }
 }
@@ -118,6 +127,47 @@ impl<'a> ArgMatches<'a> {
         None
     }
 
+    /// Gets the value of a specific argument converted into the type `R` using its
+    /// [`std::str::FromStr`] implementation. This is what [`value_t!`] uses under the hood.
+    ///
+    /// If the conversion fails, the message set with [`Arg::invalid_value_message`] is used for
+    /// the returned [`ErrorKind::ValueValidation`] error, if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let m = App::new("myapp")
+    ///     .arg(Arg::with_name("size")
+    ///         .takes_value(true))
+    ///     .arg(Arg::with_name("count")
+    ///         .long("count")
+    ///         .takes_value(true))
+    ///     .get_matches_from(vec!["myapp", "64", "--count", "lots"]);
+    ///
+    /// assert_eq!(m.value_of_t::<u32>("size").unwrap(), 64);
+    /// assert_eq!(m.value_of_t::<u32>("count").unwrap_err().kind, ErrorKind::ValueValidation);
+    /// assert_eq!(m.value_of_t::<u32>("missing").unwrap_err().kind, ErrorKind::ArgumentNotFound);
+    /// ```
+    /// [`std::str::FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+    /// [`value_t!`]: ./macro.value_t!.html
+    /// [`Arg::invalid_value_message`]: ./struct.Arg.html#method.invalid_value_message
+    /// [`ErrorKind::ValueValidation`]: ./enum.ErrorKind.html#variant.ValueValidation
+    pub fn value_of_t<R: FromStr>(&self, name: &str) -> Result<R, Error> {
+        if let Some(v) = self.value_of(name) {
+            v.parse::<R>().map_err(|_| self.invalid_value(name, v))
+        } else {
+            Err(Error::argument_not_found_auto(name))
+        }
+    }
+
+    fn invalid_value(&self, name: &str, v: &str) -> Error {
+        Error::value_validation_auto(match self.invalid_msgs.get(name) {
+            Some(msg) => msg.replace("{}", v),
+            None => format!("The argument '{}' isn't a valid value", v),
+        })
+    }
+
     /// Gets the lossy value of a specific argument. If the argument wasn't present at runtime
     /// it returns `None`. A lossy value is one which contains invalid UTF-8 code points, those
     /// invalid points will be replaced with `\u{FFFD}`
@@ -219,6 +269,40 @@ impl<'a> ArgMatches<'a> {
         None
     }
 
+    /// Gets all values of a specific argument converted into the type `R` using its
+    /// [`std::str::FromStr`] implementation, in the order they were given. This is what
+    /// [`values_t!`] uses under the hood.
+    ///
+    /// The first value which fails to convert is reported as an [`ErrorKind::ValueValidation`]
+    /// error, using the message set with [`Arg::invalid_value_message`] if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let m = App::new("myapp")
+    ///     .arg(Arg::with_name("coords")
+    ///         .long("coords")
+    ///         .takes_value(true)
+    ///         .multiple(true))
+    ///     .get_matches_from(vec!["myapp", "--coords", "1.5", "64", "3"]);
+    ///
+    /// assert_eq!(m.values_of_t::<f64>("coords").unwrap(), [1.5, 64.0, 3.0]);
+    /// assert_eq!(m.values_of_t::<u32>("coords").unwrap_err().kind, ErrorKind::ValueValidation);
+    /// ```
+    /// [`std::str::FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+    /// [`values_t!`]: ./macro.values_t!.html
+    /// [`Arg::invalid_value_message`]: ./struct.Arg.html#method.invalid_value_message
+    /// [`ErrorKind::ValueValidation`]: ./enum.ErrorKind.html#variant.ValueValidation
+    pub fn values_of_t<R: FromStr>(&self, name: &str) -> Result<Vec<R>, Error> {
+        if let Some(vals) = self.values_of(name) {
+            vals.map(|v| v.parse::<R>().map_err(|_| self.invalid_value(name, v)))
+                .collect()
+        } else {
+            Err(Error::argument_not_found_auto(name))
+        }
+    }
+
     /// Gets the lossy values of a specific argument. If the option wasn't present at runtime
     /// it returns `None`. A lossy value is one where if it contains invalid UTF-8 code points,
     /// those invalid points will be replaced with `\u{FFFD}`
@@ -321,6 +405,140 @@ impl<'a> ArgMatches<'a> {
         self.args.contains_key(name.as_ref())
     }
 
+    /// Returns `true` if *any* of the given arguments were present at runtime, otherwise `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::with_name("debug")
+    ///         .short("d"))
+    ///     .arg(Arg::with_name("verbose")
+    ///         .short("v"))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "-d"
+    ///     ]);
+    ///
+    /// assert!(m.is_present_any(&["debug", "verbose"]));
+    /// ```
+    pub fn is_present_any<S: AsRef<str>>(&self, names: &[S]) -> bool {
+        names.iter().any(|n| self.is_present(n))
+    }
+
+    /// Returns `true` if *all* of the given arguments were present at runtime, otherwise `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::with_name("debug")
+    ///         .short("d"))
+    ///     .arg(Arg::with_name("verbose")
+    ///         .short("v"))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "-d"
+    ///     ]);
+    ///
+    /// assert!(!m.is_present_all(&["debug", "verbose"]));
+    /// ```
+    pub fn is_present_all<S: AsRef<str>>(&self, names: &[S]) -> bool {
+        names.iter().all(|n| self.is_present(n))
+    }
+
+    /// Returns the number of distinct flags, options and positional arguments which were used at
+    /// runtime. Arguments which only received a [default value], [`ArgGroup`]s and the
+    /// subcommand are not counted, which makes this handy for "show the help if no arguments
+    /// were given" style checks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let app = App::new("myprog")
+    ///     .arg(Arg::with_name("verbose")
+    ///         .short("v")
+    ///         .multiple(true))
+    ///     .arg(Arg::with_name("world")
+    ///         .long("world")
+    ///         .default_value("main"))
+    ///     .arg(Arg::with_name("input"));
+    ///
+    /// let m = app.clone().get_matches_from(vec!["myprog"]);
+    /// assert_eq!(m.args_present(), 0);
+    ///
+    /// let m = app.get_matches_from(vec!["myprog", "-vv", "in.txt"]);
+    /// assert_eq!(m.args_present(), 2);
+    /// ```
+    /// [default value]: ./struct.Arg.html#method.default_value
+    /// [`ArgGroup`]: ./struct.ArgGroup.html
+    pub fn args_present(&self) -> usize {
+        // groups are never given an index, and defaults never count as an occurrence
+        self.args
+            .values()
+            .filter(|ma| ma.occurs > 0 && !ma.indices.is_empty())
+            .count()
+    }
+
+    /// Returns the name of the argument which was used from the [`ArgGroup`] `group`, or `None`
+    /// if no member of the group was used. If the group allows [multiple] members, the one used
+    /// first is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ArgGroup};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::with_name("fast")
+    ///         .long("fast"))
+    ///     .arg(Arg::with_name("slow")
+    ///         .long("slow"))
+    ///     .group(ArgGroup::with_name("mode")
+    ///         .args(&["fast", "slow"])
+    ///         .required(true))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "--slow"
+    ///     ]);
+    ///
+    /// assert_eq!(m.group_member("mode"), Some("slow"));
+    /// ```
+    /// [`ArgGroup`]: ./struct.ArgGroup.html
+    /// [multiple]: ./struct.ArgGroup.html#method.multiple
+    pub fn group_member<S: AsRef<str>>(&self, group: S) -> Option<&str> {
+        self.groups.get(group.as_ref()).map(|&n| n)
+    }
+
+    /// Resolves the final state of a [negatable] flag: `Some(true)` if it was turned on,
+    /// `Some(false)` if it was explicitly turned off (such as with `--no-<long>`), or `None` if
+    /// it wasn't used at all. When both forms were used, the one used last wins.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::with_name("color")
+    ///         .long("color")
+    ///         .negatable(true))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "--no-color"
+    ///     ]);
+    ///
+    /// assert_eq!(m.flag_state("color"), Some(false));
+    /// assert!(m.flag_state("color").unwrap_or(true) == false);
+    /// ```
+    /// [negatable]: ./struct.Arg.html#method.negatable
+    pub fn flag_state<S: AsRef<str>>(&self, name: S) -> Option<bool> {
+        if self.is_present(&name) {
+            Some(true)
+        } else if self.negated.iter().any(|&n| n == name.as_ref()) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
     /// Returns the number of times an argument was used at runtime. If an argument isn't present
     /// it will return `0`.
     ///
@@ -364,6 +582,27 @@ impl<'a> ArgMatches<'a> {
         self.args.get(name.as_ref()).map_or(0, |a| a.occurs)
     }
 
+    /// Gets the number of times a [counter] flag was used at runtime, or `0` if it wasn't used
+    /// at all.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::with_name("verbose")
+    ///         .short("v")
+    ///         .count(true))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "-vvv", "-v"
+    ///     ]);
+    ///
+    /// assert_eq!(m.count_of("verbose"), 4);
+    /// assert_eq!(m.count_of("quiet"), 0);
+    /// ```
+    /// [counter]: ./struct.Arg.html#method.count
+    pub fn count_of<S: AsRef<str>>(&self, name: S) -> u64 { self.occurrences_of(name) }
+
     /// Gets the starting index of the argument in respect to all other arguments. Indices are
     /// similar to argv indices, but are not exactly 1:1.
     ///
@@ -627,6 +866,44 @@ impl<'a> ArgMatches<'a> {
         None
     }
 
+    /// The same as [`ArgMatches::subcommand_matches`], but returns a mutable reference so the
+    /// [`ArgMatches`] of a [`SubCommand`] can be post-processed in place, for example with
+    /// [`ArgMatches::set_value`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, SubCommand};
+    /// let mut app_m = App::new("myprog")
+    ///     .subcommand(SubCommand::with_name("test")
+    ///         .arg(Arg::with_name("opt")
+    ///             .long("option")
+    ///             .takes_value(true)))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "test"
+    ///     ]);
+    ///
+    /// if let Some(sub_m) = app_m.subcommand_matches_mut("test") {
+    ///     sub_m.set_value("opt", "computed");
+    /// }
+    /// assert_eq!(app_m.subcommand_matches("test").unwrap().value_of("opt"), Some("computed"));
+    /// ```
+    /// [`ArgMatches::subcommand_matches`]: ./struct.ArgMatches.html#method.subcommand_matches
+    /// [`ArgMatches::set_value`]: ./struct.ArgMatches.html#method.set_value
+    /// [`ArgMatches`]: ./struct.ArgMatches.html
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    pub fn subcommand_matches_mut<S: AsRef<str>>(
+        &mut self,
+        name: S,
+    ) -> Option<&mut ArgMatches<'a>> {
+        if let Some(ref mut s) = self.subcommand {
+            if s.name == name.as_ref() {
+                return Some(&mut s.matches);
+            }
+        }
+        None
+    }
+
     /// Because [`Subcommand`]s are essentially "sub-[`App`]s" they have their own [`ArgMatches`]
     /// as well.But simply getting the sub-[`ArgMatches`] doesn't help much if we don't also know
     /// which subcommand was actually used. This method returns the name of the subcommand that was
@@ -744,6 +1021,10 @@ impl<'a> ArgMatches<'a> {
 
     /// Returns a string slice of the usage statement for the [`App`] or [`SubCommand`]
     ///
+    /// The usage is computed once parsing succeeds, the same way as [`App::write_usage`]. Each
+    /// [`SubCommand`]'s matches hold that subcommand's own usage, so use
+    /// [`ArgMatches::subcommand_matches`] to get the usage of the deepest matched subcommand.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -756,7 +1037,126 @@ impl<'a> ArgMatches<'a> {
     /// ```
     /// [`Subcommand`]: ./struct.SubCommand.html
     /// [`App`]: ./struct.App.html
+    /// [`App::write_usage`]: ./struct.App.html#method.write_usage
+    /// [`ArgMatches::subcommand_matches`]: ./struct.ArgMatches.html#method.subcommand_matches
     pub fn usage(&self) -> &str { self.usage.as_ref().map_or("", |u| &u[..]) }
+
+    /// Replaces all values of the argument `name` with the single value `val`. If the argument
+    /// wasn't present it will be afterwards, but (like a default value)
+    /// [`ArgMatches::occurrences_of`] will return `0` for it.
+    ///
+    /// **NOTE:** No validation is done, the value is simply stored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let mut m = App::new("myprog")
+    ///     .arg(Arg::with_name("output")
+    ///         .takes_value(true))
+    ///     .get_matches_from(vec!["myprog"]);
+    ///
+    /// m.set_value("output", "out.txt");
+    /// assert_eq!(m.value_of("output"), Some("out.txt"));
+    /// assert_eq!(m.occurrences_of("output"), 0);
+    /// ```
+    /// [`ArgMatches::occurrences_of`]: ./struct.ArgMatches.html#method.occurrences_of
+    pub fn set_value<S: Into<OsString>>(&mut self, name: &'a str, val: S) {
+        let ma = self.args.entry(name).or_insert(MatchedArg {
+            occurs: 0,
+            kind: ArgKind::Opt,
+            ..MatchedArg::new()
+        });
+        ma.vals = vec![val.into()];
+    }
+
+    /// Removes the argument `name` (and any values of it) as if it had never been used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let mut m = App::new("myprog")
+    ///     .arg(Arg::with_name("debug")
+    ///         .short("d"))
+    ///     .get_matches_from(vec!["myprog", "-d"]);
+    ///
+    /// m.unset("debug");
+    /// assert!(!m.is_present("debug"));
+    /// ```
+    pub fn unset<S: AsRef<str>>(&mut self, name: S) { self.args.remove(name.as_ref()); }
+
+    /// Checks whether two `ArgMatches` hold the same arguments, with the same number of
+    /// occurrences and values, and the same subcommand (compared recursively). The order the
+    /// arguments were given in is ignored, but the values of each argument must be in the same
+    /// order. This is mostly useful for asserting parse results in tests.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let app = App::new("myprog")
+    ///     .arg(Arg::with_name("debug").short("d"))
+    ///     .arg(Arg::with_name("world").short("w").takes_value(true).multiple(true));
+    ///
+    /// let m1 = app.clone().get_matches_from(vec!["myprog", "-d", "-w", "main", "-w", "pvp"]);
+    /// let m2 = app.clone().get_matches_from(vec!["myprog", "-w", "main", "-w", "pvp", "-d"]);
+    /// let m3 = app.get_matches_from(vec!["myprog", "-d", "-w", "pvp", "-w", "main"]);
+    ///
+    /// assert!(m1.eq_ignoring_value_order(&m2));
+    /// assert!(!m1.eq_ignoring_value_order(&m3));
+    /// ```
+    pub fn eq_ignoring_value_order(&self, other: &ArgMatches) -> bool {
+        let args_eq = self.args.len() == other.args.len()
+            && self.args.iter().all(|(name, ma)| {
+                other
+                    .args
+                    .get(name)
+                    .map_or(false, |o| ma.occurs == o.occurs && ma.vals == o.vals)
+            });
+        args_eq && match (&self.subcommand, &other.subcommand) {
+            (&Some(ref sc), &Some(ref o)) => {
+                sc.name == o.name && sc.matches.eq_ignoring_value_order(&o.matches)
+            }
+            (&None, &None) => true,
+            _ => false,
+        }
+    }
+
+    // Writes the matched args sorted by name, flags with their number of occurrences first, then
+    // options and positionals with their values. Any subcommand's matches are written below,
+    // indented by one level per depth.
+    fn fmt_nested(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+        let indent = "    ".repeat(depth);
+        let mut args: Vec<_> = self.args.iter().collect();
+        args.sort_by(|a, b| a.0.cmp(b.0));
+
+        writeln!(f, "ArgMatches {{")?;
+        write!(f, "{}    flags: {{", indent)?;
+        let flags = args.iter().filter(|&&(_, ma)| ma.kind == ArgKind::Flag);
+        for (i, &(name, ma)) in flags.enumerate() {
+            write!(f, "{} {}\u{d7}{}", if i == 0 { "" } else { "," }, name, ma.occurs)?;
+        }
+        writeln!(f, " }}")?;
+        for &(group, kind) in &[("options", ArgKind::Opt), ("positionals", ArgKind::Positional)] {
+            write!(f, "{}    {}: {{", indent, group)?;
+            for (i, &(name, ma)) in args.iter().filter(|&&(_, ma)| ma.kind == kind).enumerate() {
+                let vals: Vec<_> = ma.vals.iter().map(|v| v.to_string_lossy()).collect();
+                write!(f, "{} {}: {:?}", if i == 0 { "" } else { "," }, name, vals)?;
+            }
+            writeln!(f, " }}")?;
+        }
+        if let Some(ref sc) = self.subcommand {
+            write!(f, "{}    subcommand: {} ", indent, sc.name)?;
+            sc.matches.fmt_nested(f, depth + 1)?;
+            writeln!(f)?;
+        }
+        write!(f, "{}}}", indent)
+    }
+}
+
+impl<'a> fmt::Debug for ArgMatches<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { self.fmt_nested(f, 0) }
 }
 
 
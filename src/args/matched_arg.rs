@@ -1,12 +1,21 @@
 // Std
 use std::ffi::OsString;
 
+#[doc(hidden)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ArgKind {
+    Flag,
+    Opt,
+    Positional,
+}
+
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 pub struct MatchedArg {
     #[doc(hidden)] pub occurs: u64,
     #[doc(hidden)] pub indices: Vec<usize>,
     #[doc(hidden)] pub vals: Vec<OsString>,
+    #[doc(hidden)] pub kind: ArgKind,
 }
 
 impl Default for MatchedArg {
@@ -15,6 +24,7 @@ impl Default for MatchedArg {
             occurs: 1,
             indices: Vec::new(),
             vals: Vec::new(),
+            kind: ArgKind::Flag,
         }
     }
 }
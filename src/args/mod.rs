@@ -4,9 +4,10 @@ pub use self::arg_builder::{Base, FlagBuilder, OptBuilder, PosBuilder, Switched,
 pub use self::arg_matcher::ArgMatcher;
 pub use self::arg_matches::{ArgMatches, OsValues, Values};
 pub use self::group::ArgGroup;
-pub use self::matched_arg::MatchedArg;
+pub use self::matched_arg::{ArgKind, MatchedArg};
 pub use self::settings::{ArgFlags, ArgSettings};
 pub use self::subcommand::SubCommand;
+pub use self::token_kind::TokenKind;
 
 #[macro_use]
 mod macros;
@@ -18,4 +19,5 @@ mod subcommand;
 mod arg_builder;
 mod matched_arg;
 mod group;
+mod token_kind;
 pub mod settings;
@@ -25,6 +25,8 @@ bitflags! {
         const HIDE_ENV_VALS    = 1 << 17;
         const HIDDEN_SHORT_H   = 1 << 18;
         const HIDDEN_LONG_H    = 1 << 19;
+        const NEGATABLE        = 1 << 20;
+        const COUNT            = 1 << 21;
     }
 }
 
@@ -55,7 +57,9 @@ impl ArgFlags {
         HideEnvValues => Flags::HIDE_ENV_VALS,
         HideDefaultValue => Flags::HIDE_DEFAULT_VAL,
         HiddenShortHelp => Flags::HIDDEN_SHORT_H,
-        HiddenLongHelp => Flags::HIDDEN_LONG_H
+        HiddenLongHelp => Flags::HIDDEN_LONG_H,
+        Negatable => Flags::NEGATABLE,
+        Count => Flags::COUNT
     }
 }
 
@@ -110,6 +114,10 @@ pub enum ArgSettings {
     HiddenShortHelp,
     /// The argument should **not** be shown in long help text
     HiddenLongHelp,
+    /// The flag may be explicitly turned off with `--no-<long>` or `--<long>=false`
+    Negatable,
+    /// The flag counts how many times it was used, such as `-vvv`
+    Count,
     #[doc(hidden)] RequiredUnlessAll,
     #[doc(hidden)] ValueDelimiterNotSet,
 }
@@ -138,6 +146,8 @@ impl FromStr for ArgSettings {
             "hideenvvalues" => Ok(ArgSettings::HideEnvValues),
             "hiddenshorthelp" => Ok(ArgSettings::HiddenShortHelp),
             "hiddenlonghelp" => Ok(ArgSettings::HiddenLongHelp),
+            "negatable" => Ok(ArgSettings::Negatable),
+            "count" => Ok(ArgSettings::Count),
             _ => Err("unknown ArgSetting, cannot convert from str".to_owned()),
         }
     }
@@ -226,6 +236,14 @@ mod test {
             "hiddenlonghelp".parse::<ArgSettings>().unwrap(),
             ArgSettings::HiddenLongHelp
         );
+        assert_eq!(
+            "negatable".parse::<ArgSettings>().unwrap(),
+            ArgSettings::Negatable
+        );
+        assert_eq!(
+            "count".parse::<ArgSettings>().unwrap(),
+            ArgSettings::Count
+        );
         assert!("hahahaha".parse::<ArgSettings>().is_err());
     }
 }
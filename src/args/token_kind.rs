@@ -0,0 +1,17 @@
+/// The role a single command line token played while parsing, as reported by
+/// [`App::get_matches_with_spans`]
+///
+/// [`App::get_matches_with_spans`]: ./struct.App.html#method.get_matches_with_spans
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TokenKind {
+    /// A flag, such as `-v`, `--verbose` or a group of short flags like `-vvq`
+    Flag,
+    /// An option, including any value attached to it such as in `--name=value` or `-nvalue`
+    Opt,
+    /// A value belonging to the preceding option, or an argument to an external subcommand
+    Value,
+    /// A positional argument
+    Positional,
+    /// The name (or alias) of a subcommand
+    Subcommand,
+}
@@ -58,8 +58,9 @@ fn gen_fish_inner(root_command: &str, comp_gen: &FishGen, subcommand: &str, buff
         if let Some(data) = option.b.help {
             template.push_str(format!(" -d '{}'", escape_string(data)).as_str());
         }
+        template.push_str(" -r");
         if let Some(ref data) = option.v.possible_vals {
-            template.push_str(format!(" -r -f -a \"{}\"", data.join(" ")).as_str());
+            template.push_str(format!(" -f -a \"{}\"", data.join(" ")).as_str());
         }
         buffer.push_str(template.as_str());
         buffer.push_str("\n");
@@ -80,6 +81,20 @@ fn gen_fish_inner(root_command: &str, comp_gen: &FishGen, subcommand: &str, buff
         buffer.push_str("\n");
     }
 
+    for positional in comp_gen.p.positionals() {
+        let data = match positional.v.possible_vals {
+            Some(ref data) => data,
+            None => continue,
+        };
+        let mut template = basic_template.clone();
+        template.push_str(format!(" -f -a \"{}\"", data.join(" ")).as_str());
+        if let Some(data) = positional.b.help {
+            template.push_str(format!(" -d '{}'", escape_string(data)).as_str());
+        }
+        buffer.push_str(template.as_str());
+        buffer.push_str("\n");
+    }
+
     for subcommand in &comp_gen.p.subcommands {
         let mut template = basic_template.clone();
         template.push_str(" -f");
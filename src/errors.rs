@@ -9,7 +9,8 @@ use std::result::Result as StdResult;
 
 // Internal
 use args::AnyArg;
-use fmt::{ColorWhen, Colorizer, ColorizerOption};
+use fmt::{ColorWhen, Colorizer, ColorizerOption, Colors};
+use output::{self, OutputKind, OutputSink};
 use suggestions;
 
 /// Short hand for [`Result`] type
@@ -320,6 +321,23 @@ pub enum ErrorKind {
     /// [`AppSettings::StrictUtf8`]: ./enum.AppSettings.html#variant.StrictUtf8
     InvalidUtf8,
 
+    /// Occurs when the input exceeds one of the bounds set with [`App::parse_limits`], such as
+    /// too many arguments, an overly long argument or too many values for a single argument.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind, ParseLimits};
+    /// let result = App::new("prog")
+    ///     .parse_limits(ParseLimits::new().max_token_len(8))
+    ///     .arg(Arg::with_name("name"))
+    ///     .get_matches_from_safe(vec!["prog", "a_very_long_name"]);
+    /// assert!(result.is_err());
+    /// assert_eq!(result.unwrap_err().kind, ErrorKind::LimitExceeded);
+    /// ```
+    /// [`App::parse_limits`]: ./struct.App.html#method.parse_limits
+    LimitExceeded,
+
     /// Not a true "error" as it means `--help` or similar was used.
     /// The help message will be sent to `stdout`.
     ///
@@ -359,7 +377,9 @@ pub enum ErrorKind {
     ArgumentNotFound,
 
     /// Represents an [I/O error].
-    /// Can occur when writing to `stderr` or `stdout` or reading a configuration file.
+    /// Can occur when writing to `stderr` or `stdout`, reading a configuration file or reading a
+    /// response file (see [`AppSettings::AllowResponseFiles`]).
+    /// [`AppSettings::AllowResponseFiles`]: ./enum.AppSettings.html#variant.AllowResponseFiles
     /// [I/O error]: https://doc.rust-lang.org/std/io/struct.Error.html
     Io,
 
@@ -405,22 +425,40 @@ impl Error {
     #[doc(hidden)]
     pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> { write!(w, "{}", self.message) }
 
+    /// Writes the error message to an [`OutputSink`], line by line. Help messages are written as
+    /// [`OutputKind::Info`], everything else as [`OutputKind::Error`]
+    ///
+    /// [`OutputSink`]: ./trait.OutputSink.html
+    /// [`OutputKind::Info`]: ./enum.OutputKind.html#variant.Info
+    /// [`OutputKind::Error`]: ./enum.OutputKind.html#variant.Error
+    pub fn write_to_sink(&self, sink: &OutputSink) {
+        let kind = if self.use_stderr() {
+            OutputKind::Error
+        } else {
+            OutputKind::Info
+        };
+        output::write_lines(sink, &*self.message, kind);
+    }
+
     #[doc(hidden)]
     pub fn argument_conflict<O, U>(
         arg: &AnyArg,
         other: Option<O>,
         usage: U,
-        color: ColorWhen,
+        color: Colors,
     ) -> Self
     where
         O: Into<String>,
         U: Display,
     {
         let mut v = vec![arg.name().to_owned()];
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
         Error {
             message: format!(
                 "{} The argument '{}' cannot be used with {}\n\n\
@@ -437,7 +475,7 @@ impl Error {
                     None => c.none("one or more of the other specified arguments".to_owned()),
                 },
                 usage,
-                c.good("--help")
+                c.info("--help")
             ),
             kind: ErrorKind::ArgumentConflict,
             info: Some(v),
@@ -445,14 +483,17 @@ impl Error {
     }
 
     #[doc(hidden)]
-    pub fn empty_value<U>(arg: &AnyArg, usage: U, color: ColorWhen) -> Self
+    pub fn empty_value<U>(arg: &AnyArg, usage: U, color: Colors) -> Self
     where
         U: Display,
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
         Error {
             message: format!(
                 "{} The argument '{}' requires a value but none was supplied\
@@ -462,7 +503,7 @@ impl Error {
                 c.error("error:"),
                 c.warning(arg.to_string()),
                 usage,
-                c.good("--help")
+                c.info("--help")
             ),
             kind: ErrorKind::EmptyValue,
             info: Some(vec![arg.name().to_owned()]),
@@ -475,18 +516,21 @@ impl Error {
         good_vals: &[G],
         arg: &AnyArg,
         usage: U,
-        color: ColorWhen,
+        color: Colors,
     ) -> Self
     where
         B: AsRef<str>,
         G: AsRef<str> + Display,
         U: Display,
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
-        let suffix = suggestions::did_you_mean_value_suffix(bad_val.as_ref(), good_vals.iter());
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
+        let suffix = suggestions::did_you_mean_value_suffix(bad_val.as_ref(), good_vals.iter(), color);
 
         let mut sorted = vec![];
         for v in good_vals {
@@ -508,20 +552,73 @@ impl Error {
                 valid_values,
                 suffix.0,
                 usage,
-                c.good("--help")
+                c.info("--help")
             ),
             kind: ErrorKind::InvalidValue,
             info: Some(vec![arg.name().to_owned(), bad_val.as_ref().to_owned()]),
         }
     }
 
+    #[doc(hidden)]
+    pub fn invalid_values<B, G, U>(
+        bad_vals: &[B],
+        good_vals: &[G],
+        arg: &AnyArg,
+        usage: U,
+        color: Colors,
+    ) -> Self
+    where
+        B: AsRef<str>,
+        G: AsRef<str> + Display,
+        U: Display,
+    {
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
+
+        let mut sorted = vec![];
+        for v in good_vals {
+            let val = format!("{}", c.good(v));
+            sorted.push(val);
+        }
+        sorted.sort();
+        let valid_values = sorted.join(", ");
+        let bad_values = bad_vals
+            .iter()
+            .map(|v| format!("'{}'", c.warning(v.as_ref())))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut info = vec![arg.name().to_owned()];
+        info.extend(bad_vals.iter().map(|v| v.as_ref().to_owned()));
+        Error {
+            message: format!(
+                "{} {} aren't valid values for '{}'\n\t\
+                 [possible values: {}]\n\n\
+                 {}\n\n\
+                 For more information try {}",
+                c.error("error:"),
+                bad_values,
+                c.warning(arg.to_string()),
+                valid_values,
+                usage,
+                c.info("--help")
+            ),
+            kind: ErrorKind::InvalidValue,
+            info: Some(info),
+        }
+    }
+
     #[doc(hidden)]
     pub fn invalid_subcommand<S, D, N, U>(
         subcmd: S,
         did_you_mean: D,
         name: N,
         usage: U,
-        color: ColorWhen,
+        color: Colors,
     ) -> Self
     where
         S: Into<String>,
@@ -530,10 +627,13 @@ impl Error {
         U: Display,
     {
         let s = subcmd.into();
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
         Error {
             message: format!(
                 "{} The subcommand '{}' wasn't recognized\n\t\
@@ -549,7 +649,7 @@ impl Error {
                 c.good("--"),
                 &*s,
                 usage,
-                c.good("--help")
+                c.info("--help")
             ),
             kind: ErrorKind::InvalidSubcommand,
             info: Some(vec![s]),
@@ -557,16 +657,19 @@ impl Error {
     }
 
     #[doc(hidden)]
-    pub fn unrecognized_subcommand<S, N>(subcmd: S, name: N, color: ColorWhen) -> Self
+    pub fn unrecognized_subcommand<S, N>(subcmd: S, name: N, color: Colors) -> Self
     where
         S: Into<String>,
         N: Display,
     {
         let s = subcmd.into();
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
         Error {
             message: format!(
                 "{} The subcommand '{}' wasn't recognized\n\n\
@@ -577,7 +680,7 @@ impl Error {
                 c.warning(&*s),
                 c.warning("USAGE:"),
                 name,
-                c.good("--help")
+                c.info("--help")
             ),
             kind: ErrorKind::UnrecognizedSubcommand,
             info: Some(vec![s]),
@@ -585,15 +688,18 @@ impl Error {
     }
 
     #[doc(hidden)]
-    pub fn missing_required_argument<R, U>(required: R, usage: U, color: ColorWhen) -> Self
+    pub fn missing_required_argument<R, U>(required: R, usage: U, color: Colors) -> Self
     where
         R: Display,
         U: Display,
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
         Error {
             message: format!(
                 "{} The following required arguments were not provided:{}\n\n\
@@ -602,7 +708,7 @@ impl Error {
                 c.error("error:"),
                 required,
                 usage,
-                c.good("--help")
+                c.info("--help")
             ),
             kind: ErrorKind::MissingRequiredArgument,
             info: None,
@@ -610,15 +716,18 @@ impl Error {
     }
 
     #[doc(hidden)]
-    pub fn missing_subcommand<N, U>(name: N, usage: U, color: ColorWhen) -> Self
+    pub fn missing_subcommand<N, U>(name: N, usage: U, color: Colors) -> Self
     where
         N: AsRef<str> + Display,
         U: Display,
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
         Error {
             message: format!(
                 "{} '{}' requires a subcommand, but one was not provided\n\n\
@@ -627,7 +736,7 @@ impl Error {
                 c.error("error:"),
                 c.warning(name),
                 usage,
-                c.good("--help")
+                c.info("--help")
             ),
             kind: ErrorKind::MissingSubcommand,
             info: None,
@@ -636,14 +745,17 @@ impl Error {
 
 
     #[doc(hidden)]
-    pub fn invalid_utf8<U>(usage: U, color: ColorWhen) -> Self
+    pub fn invalid_utf8<U>(usage: U, color: Colors) -> Self
     where
         U: Display,
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
         Error {
             message: format!(
                 "{} Invalid UTF-8 was detected in one or more arguments\n\n\
@@ -651,7 +763,7 @@ impl Error {
                  For more information try {}",
                 c.error("error:"),
                 usage,
-                c.good("--help")
+                c.info("--help")
             ),
             kind: ErrorKind::InvalidUtf8,
             info: None,
@@ -659,16 +771,66 @@ impl Error {
     }
 
     #[doc(hidden)]
-    pub fn too_many_values<V, U>(val: V, arg: &AnyArg, usage: U, color: ColorWhen) -> Self
+    pub fn limit_exceeded(what: &str, max: usize, arg: Option<&AnyArg>, color: Colors) -> Self {
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
+        let mut info = vec![what.to_owned(), max.to_string()];
+        if let Some(a) = arg {
+            info.push(a.name().to_owned());
+        }
+        Error {
+            message: format!(
+                "{} The maximum number of {} ({}) was exceeded{}",
+                c.error("error:"),
+                what,
+                c.warning(max.to_string()),
+                arg.map_or(String::new(), |a| format!(" for '{}'", c.warning(a.to_string())))
+            ),
+            kind: ErrorKind::LimitExceeded,
+            info: Some(info),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn response_file<E: Display>(path: &str, reason: E, color: Colors) -> Self {
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
+        Error {
+            message: format!(
+                "{} Couldn't read the response file '{}': {}",
+                c.error("error:"),
+                c.warning(path),
+                reason
+            ),
+            kind: ErrorKind::Io,
+            info: Some(vec![path.to_owned()]),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn too_many_values<V, U>(val: V, arg: &AnyArg, usage: U, color: Colors) -> Self
     where
         V: AsRef<str> + Display + ToOwned,
         U: Display,
     {
         let v = val.as_ref();
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
         Error {
             message: format!(
                 "{} The value '{}' was provided to '{}', but it wasn't expecting \
@@ -679,7 +841,7 @@ impl Error {
                 c.warning(v),
                 c.warning(arg.to_string()),
                 usage,
-                c.good("--help")
+                c.info("--help")
             ),
             kind: ErrorKind::TooManyValues,
             info: Some(vec![arg.name().to_owned(), v.to_owned()]),
@@ -692,15 +854,18 @@ impl Error {
         min_vals: u64,
         curr_vals: usize,
         usage: U,
-        color: ColorWhen,
+        color: Colors,
     ) -> Self
     where
         U: Display,
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
         Error {
             message: format!(
                 "{} The argument '{}' requires at least {} values, but only {} w{} \
@@ -713,7 +878,7 @@ impl Error {
                 c.warning(curr_vals.to_string()),
                 if curr_vals > 1 { "ere" } else { "as" },
                 usage,
-                c.good("--help")
+                c.info("--help")
             ),
             kind: ErrorKind::TooFewValues,
             info: Some(vec![arg.name().to_owned()]),
@@ -721,12 +886,15 @@ impl Error {
     }
 
     #[doc(hidden)]
-    pub fn value_validation(arg: Option<&AnyArg>, err: String, color: ColorWhen) -> Self
+    pub fn value_validation(arg: Option<&AnyArg>, err: String, color: Colors) -> Self
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
         Error {
             message: format!(
                 "{} Invalid value{}: {}",
@@ -743,10 +911,37 @@ impl Error {
         }
     }
 
+    #[doc(hidden)]
+    pub fn custom_validation<U>(err: String, usage: U, color: Colors) -> Self
+    where
+        U: Display,
+    {
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
+        Error {
+            message: format!(
+                "{} {}\n\n\
+                 {}\n\n\
+                 For more information try {}",
+                c.error("error:"),
+                err,
+                usage,
+                c.info("--help")
+            ),
+            kind: ErrorKind::ValueValidation,
+            info: None,
+        }
+    }
+
     #[doc(hidden)]
     pub fn value_validation_auto(err: String) -> Self {
         let n: Option<&AnyArg> = None;
-        Error::value_validation(n, err, ColorWhen::Auto)
+        Error::value_validation(n, err, ColorWhen::Auto.into())
     }
 
     #[doc(hidden)]
@@ -756,16 +951,19 @@ impl Error {
         curr_vals: usize,
         suffix: S,
         usage: U,
-        color: ColorWhen,
+        color: Colors,
     ) -> Self
     where
         S: Display,
         U: Display,
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
         Error {
             message: format!(
                 "{} The argument '{}' requires {} values, but {} w{} \
@@ -778,7 +976,7 @@ impl Error {
                 c.warning(curr_vals.to_string()),
                 suffix,
                 usage,
-                c.good("--help")
+                c.info("--help")
             ),
             kind: ErrorKind::WrongNumberOfValues,
             info: Some(vec![arg.name().to_owned()]),
@@ -786,14 +984,17 @@ impl Error {
     }
 
     #[doc(hidden)]
-    pub fn unexpected_multiple_usage<U>(arg: &AnyArg, usage: U, color: ColorWhen) -> Self
+    pub fn unexpected_multiple_usage<U>(arg: &AnyArg, usage: U, color: Colors) -> Self
     where
         U: Display,
     {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
         Error {
             message: format!(
                 "{} The argument '{}' was provided more than once, but cannot \
@@ -803,7 +1004,7 @@ impl Error {
                 c.error("error:"),
                 c.warning(arg.to_string()),
                 usage,
-                c.good("--help")
+                c.info("--help")
             ),
             kind: ErrorKind::UnexpectedMultipleUsage,
             info: Some(vec![arg.name().to_owned()]),
@@ -811,16 +1012,19 @@ impl Error {
     }
 
     #[doc(hidden)]
-    pub fn unknown_argument<A, U>(arg: A, did_you_mean: &str, usage: U, color: ColorWhen) -> Self
+    pub fn unknown_argument<A, U>(arg: A, did_you_mean: &str, usage: U, color: Colors) -> Self
     where
         A: Into<String>,
         U: Display,
     {
         let a = arg.into();
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
         Error {
             message: format!(
                 "{} Found argument '{}' which wasn't expected, or isn't valid in \
@@ -835,7 +1039,7 @@ impl Error {
                     format!("{}\n", did_you_mean)
                 },
                 usage,
-                c.good("--help")
+                c.info("--help")
             ),
             kind: ErrorKind::UnknownArgument,
             info: Some(vec![a]),
@@ -843,11 +1047,14 @@ impl Error {
     }
 
     #[doc(hidden)]
-    pub fn io_error(e: &Error, color: ColorWhen) -> Self {
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: color,
-        });
+    pub fn io_error(e: &Error, color: Colors) -> Self {
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: color.when,
+            },
+            color.palette,
+        );
         Error {
             message: format!("{} {}", c.error("error:"), e.description()),
             kind: ErrorKind::Io,
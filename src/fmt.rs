@@ -5,6 +5,7 @@ struct ANSIString {
     s: String,
 }
 impl ANSIString {
+    #[cfg(feature = "color")]
     pub fn new<S: Into<String>>(color_code: &'static str, s: S) -> Self {
         Self {
             color_code: Some(color_code),
@@ -36,17 +37,34 @@ impl fmt::Display for ANSIString {
     }
 }
 
-enum Color {
+/// A color which output can be styled with, as picked for each kind of text by a [`Palette`]
+///
+/// [`Palette`]: ./struct.Palette.html
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Color {
+    /// Green (`&a` in the ClassiCube chat)
     Green,
+    /// Red (`&c` in the ClassiCube chat)
     RedBold,
+    /// Yellow (`&e` in the ClassiCube chat)
     Yellow,
+    /// Blue (`&9` in the ClassiCube chat)
+    Blue,
+    /// Cyan (`&b` in the ClassiCube chat)
+    Cyan,
+    /// Magenta (`&d` in the ClassiCube chat)
+    Magenta,
 }
 impl Color {
-    pub fn paint(&self, s: &str) -> ANSIString {
+    #[cfg(feature = "color")]
+    fn paint(&self, s: &str) -> ANSIString {
         let color_code = match self {
             Color::Green => classicube_helpers::color::LIME,
             Color::RedBold => classicube_helpers::color::RED,
             Color::Yellow => classicube_helpers::color::YELLOW,
+            Color::Blue => classicube_helpers::color::BLUE,
+            Color::Cyan => classicube_helpers::color::AQUA,
+            Color::Magenta => classicube_helpers::color::PINK,
         };
 
         ANSIString::new(color_code, s)
@@ -61,36 +79,104 @@ pub enum ColorWhen {
     Never,
 }
 
+// When to color output, along with the colors to use
+#[doc(hidden)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Colors {
+    pub when: ColorWhen,
+    pub palette: Palette,
+}
+
+impl From<ColorWhen> for Colors {
+    fn from(when: ColorWhen) -> Self {
+        Colors {
+            when: when,
+            palette: Palette::default(),
+        }
+    }
+}
+
 #[doc(hidden)]
+#[derive(Debug, Copy, Clone)]
 pub struct ColorizerOption {
     pub use_stderr: bool,
     pub when: ColorWhen,
 }
 
+/// The colors used for each kind of highlighted text in help messages and errors, as set with
+/// [`App::palette`]. Defaults to green for good values (such as valid arguments), yellow for
+/// warnings (such as the argument at fault), red for errors and cyan for notices (such as the
+/// `--help` hint).
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{Color, Palette};
+/// let palette = Palette {
+///     error: Color::Magenta,
+///     ..Palette::default()
+/// };
+/// assert_eq!(palette.warning, Color::Yellow);
+/// ```
+/// [`App::palette`]: ./struct.App.html#method.palette
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Palette {
+    /// The color of good values, such as valid arguments and default values
+    pub good: Color,
+    /// The color of warnings, such as the argument which caused an error
+    pub warning: Color,
+    /// The color of the `error:` prefix of errors
+    pub error: Color,
+    /// The color of neutral notices, such as the `--help` hint below errors
+    pub info: Color,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette {
+            good: Color::Green,
+            warning: Color::Yellow,
+            error: Color::RedBold,
+            info: Color::Cyan,
+        }
+    }
+}
+
 #[doc(hidden)]
+#[derive(Debug, Copy, Clone)]
 pub struct Colorizer {
     when: ColorWhen,
+    palette: Palette,
 }
 
 macro_rules! color {
     ($_self:ident, $c:ident, $m:expr) => {
         match $_self.when {
-            ColorWhen::Auto => Format::$c($m),
-            ColorWhen::Always => Format::$c($m),
+            ColorWhen::Auto => Format::Colored($_self.palette.$c, $m),
+            ColorWhen::Always => Format::Colored($_self.palette.$c, $m),
             ColorWhen::Never => Format::None($m),
         }
     };
 }
 
 impl Colorizer {
-    pub fn new(option: ColorizerOption) -> Colorizer { Colorizer { when: option.when } }
+    pub fn new(option: ColorizerOption) -> Colorizer {
+        Colorizer::with_palette(option, Palette::default())
+    }
+
+    pub fn with_palette(option: ColorizerOption, palette: Palette) -> Colorizer {
+        Colorizer {
+            when: option.when,
+            palette: palette,
+        }
+    }
 
     pub fn good<T>(&self, msg: T) -> Format<T>
     where
         T: fmt::Display + AsRef<str>,
     {
         debugln!("Colorizer::good;");
-        color!(self, Good, msg)
+        color!(self, good, msg)
     }
 
     pub fn warning<T>(&self, msg: T) -> Format<T>
@@ -98,7 +184,7 @@ impl Colorizer {
         T: fmt::Display + AsRef<str>,
     {
         debugln!("Colorizer::warning;");
-        color!(self, Warning, msg)
+        color!(self, warning, msg)
     }
 
     pub fn error<T>(&self, msg: T) -> Format<T>
@@ -106,7 +192,15 @@ impl Colorizer {
         T: fmt::Display + AsRef<str>,
     {
         debugln!("Colorizer::error;");
-        color!(self, Error, msg)
+        color!(self, error, msg)
+    }
+
+    pub fn info<T>(&self, msg: T) -> Format<T>
+    where
+        T: fmt::Display + AsRef<str>,
+    {
+        debugln!("Colorizer::info;");
+        color!(self, info, msg)
     }
 
     pub fn none<T>(&self, msg: T) -> Format<T>
@@ -128,7 +222,7 @@ impl Default for Colorizer {
 }
 
 /// Defines styles for different types of error messages. Defaults to Error=Red, Warning=Yellow,
-/// and Good=Green
+/// Good=Green and Info=Cyan
 #[derive(Debug)]
 #[doc(hidden)]
 pub enum Format<T> {
@@ -138,16 +232,40 @@ pub enum Format<T> {
     Warning(T),
     /// Defines the style used for good values, defaults to Green
     Good(T),
+    /// Defines the style used for neutral notices, defaults to Cyan
+    Info(T),
+    /// Defines a specific color, such as the one a [`Palette`] picked
+    ///
+    /// [`Palette`]: ./struct.Palette.html
+    Colored(Color, T),
     /// Defines no formatting style
     None(T),
 }
 
+#[cfg(feature = "color")]
+impl<T: AsRef<str>> Format<T> {
+    fn format(&self) -> ANSIString {
+        let palette = Palette::default();
+        match *self {
+            Format::Error(ref e) => palette.error.paint(e.as_ref()),
+            Format::Warning(ref e) => palette.warning.paint(e.as_ref()),
+            Format::Good(ref e) => palette.good.paint(e.as_ref()),
+            Format::Info(ref e) => palette.info.paint(e.as_ref()),
+            Format::Colored(ref c, ref e) => c.paint(e.as_ref()),
+            Format::None(ref e) => ANSIString::from(e.as_ref()),
+        }
+    }
+}
+
+#[cfg(not(feature = "color"))]
 impl<T: AsRef<str>> Format<T> {
     fn format(&self) -> ANSIString {
         match *self {
-            Format::Error(ref e) => Color::RedBold.paint(e.as_ref()),
-            Format::Warning(ref e) => Color::Yellow.paint(e.as_ref()),
-            Format::Good(ref e) => Color::Green.paint(e.as_ref()),
+            Format::Error(ref e) => ANSIString::from(e.as_ref()),
+            Format::Warning(ref e) => ANSIString::from(e.as_ref()),
+            Format::Good(ref e) => ANSIString::from(e.as_ref()),
+            Format::Info(ref e) => ANSIString::from(e.as_ref()),
+            Format::Colored(_, ref e) => ANSIString::from(e.as_ref()),
             Format::None(ref e) => ANSIString::from(e.as_ref()),
         }
     }
@@ -178,10 +296,77 @@ mod test {
             &*format!("{}", warn),
             &*format!("{}", Color::Yellow.paint("warn"))
         );
+        let info = Format::Info("info");
+        assert_eq!(
+            &*format!("{}", info),
+            &*format!("{}", Color::Cyan.paint("info"))
+        );
         let none = Format::None("none");
         assert_eq!(
             &*format!("{}", none),
             &*format!("{}", ANSIString::from("none"))
         );
     }
+
+    #[test]
+    fn colored_output_extra_colors() {
+        let blue = Format::Colored(Color::Blue, "blue");
+        assert_eq!(&*format!("{}", blue), "&9blue&f");
+        let cyan = Format::Colored(Color::Cyan, "cyan");
+        assert_eq!(&*format!("{}", cyan), "&bcyan&f");
+        let magenta = Format::Colored(Color::Magenta, "magenta");
+        assert_eq!(&*format!("{}", magenta), "&dmagenta&f");
+    }
+
+    #[test]
+    fn colorizer_palette() {
+        let c = Colorizer::with_palette(
+            ColorizerOption {
+                use_stderr: true,
+                when: ColorWhen::Always,
+            },
+            Palette {
+                good: Color::Cyan,
+                warning: Color::Magenta,
+                error: Color::Blue,
+                info: Color::Green,
+            },
+        );
+        assert_eq!(&*format!("{}", c.good("good")), &*format!("{}", Color::Cyan.paint("good")));
+        assert_eq!(&*format!("{}", c.warning("warn")), &*format!("{}", Color::Magenta.paint("warn")));
+        assert_eq!(&*format!("{}", c.error("error")), &*format!("{}", Color::Blue.paint("error")));
+        assert_eq!(&*format!("{}", c.info("info")), &*format!("{}", Color::Green.paint("info")));
+
+        let c = Colorizer::default();
+        assert_eq!(&*format!("{}", c.good("good")), &*format!("{}", Format::Good("good")));
+        assert_eq!(&*format!("{}", c.error("error")), &*format!("{}", Format::Error("error")));
+        assert_eq!(&*format!("{}", c.info("info")), &*format!("{}", Format::Info("info")));
+
+        let c = Colorizer::new(ColorizerOption {
+            use_stderr: true,
+            when: ColorWhen::Never,
+        });
+        assert_eq!(&*format!("{}", c.info("info")), "info");
+    }
+}
+
+#[cfg(all(test, not(feature = "color")))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn no_colored_output() {
+        assert_eq!(&*format!("{}", Format::Error("error")), "error");
+        assert_eq!(&*format!("{}", Format::Warning("warn")), "warn");
+        assert_eq!(&*format!("{}", Format::Good("good")), "good");
+        assert_eq!(&*format!("{}", Format::Info("info")), "info");
+        assert_eq!(&*format!("{}", Format::None("none")), "none");
+        assert_eq!(&*format!("{}", Format::Colored(Color::Blue, "blue")), "blue");
+
+        let c = Colorizer::new(ColorizerOption {
+            use_stderr: true,
+            when: ColorWhen::Always,
+        });
+        assert_eq!(&*format!("{}", c.error("error:")), "error:");
+    }
 }
@@ -328,7 +328,7 @@
 //! #### Features enabled by default
 //!
 //! * `suggestions`: Turns on the `Did you mean '--myoption'?` feature for when users make typos. (builds dependency `strsim`)
-//! * `color`: Turns on colored error messages. This feature only works on non-Windows OSs. (builds dependency `ansi-term` and `atty`)
+//! * `color`: Turns on colored help and error messages using ClassiCube chat color codes. Without it all output is plain text.
 //! * `wrap_help`: Wraps the help at the actual terminal width when
 //!  available, instead of 120 characters. (builds dependency `textwrap`
 //! with feature `term_size`)
@@ -545,11 +545,12 @@ extern crate yaml_rust;
 
 #[cfg(feature = "yaml")]
 pub use yaml_rust::YamlLoader;
-pub use args::{Arg, ArgGroup, ArgMatches, ArgSettings, OsValues, SubCommand, Values};
-pub use app::{App, AppSettings};
-pub use fmt::Format;
+pub use args::{Arg, ArgGroup, ArgMatches, ArgSettings, OsValues, SubCommand, TokenKind, Values};
+pub use app::{App, AppSettings, ParseLimits};
+pub use fmt::{Color, ColorWhen, Colorizer, ColorizerOption, Format, Palette};
 pub use errors::{Error, ErrorKind, Result};
 pub use completions::Shell;
+pub use output::{OutputKind, OutputSink};
 
 #[macro_use]
 mod macros;
@@ -563,6 +564,8 @@ mod osstringext;
 mod strext;
 mod completions;
 mod map;
+mod output;
+pub mod prelude;
 
 const INTERNAL_ERROR_MSG: &'static str = "Fatal internal error. Please consider filing a bug \
                                           report at https://github.com/clap-rs/clap/issues";
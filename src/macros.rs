@@ -63,16 +63,7 @@ macro_rules! value_t {
         value_t!($m.value_of($v), $t)
     };
     ($m:ident.value_of($v:expr), $t:ty) => {
-        if let Some(v) = $m.value_of($v) {
-            match v.parse::<$t>() {
-                Ok(val) => Ok(val),
-                Err(_)  =>
-                    Err(::clap::Error::value_validation_auto(
-                        format!("The argument '{}' isn't a valid value", v))),
-            }
-        } else {
-            Err(::clap::Error::argument_not_found_auto($v))
-        }
+        $m.value_of_t::<$t>(&*$v)
     };
 }
 
@@ -156,26 +147,7 @@ macro_rules! values_t {
         values_t!($m.values_of($v), $t)
     };
     ($m:ident.values_of($v:expr), $t:ty) => {
-        if let Some(vals) = $m.values_of($v) {
-            let mut tmp = vec![];
-            let mut err = None;
-            for pv in vals {
-                match pv.parse::<$t>() {
-                    Ok(rv) => tmp.push(rv),
-                    Err(..) => {
-                        err = Some(::clap::Error::value_validation_auto(
-                                format!("The argument '{}' isn't a valid value", pv)));
-                        break
-                    }
-                }
-            }
-            match err {
-                Some(e) => Err(e),
-                None => Ok(tmp),
-            }
-        } else {
-            Err(::clap::Error::argument_not_found_auto($v))
-        }
+        $m.values_of_t::<$t>(&*$v)
     };
 }
 
@@ -1025,8 +997,10 @@ macro_rules! _find_by_short {
     ($_self:ident, $short:expr, $what:ident) => {{
         $_self.$what
             .iter()
-            .filter(|a| a.s.short.is_some())
-            .find(|a| a.s.short.unwrap() == $short)
+            .find(|a| {
+                a.s.short == Some($short)
+                    || a.s.shorts.as_ref().map_or(false, |s| s.contains(&$short))
+            })
     }}
 }
 
@@ -1050,7 +1024,11 @@ macro_rules! find_subcmd {
 
 macro_rules! shorts {
     ($_self:ident) => {{
-        _shorts_longs!($_self, short)
+        $_self.flags
+                .iter()
+                .map(|f| &f.s)
+                .chain($_self.opts.iter().map(|o| &o.s))
+                .flat_map(|s| s.short.iter().chain(s.shorts.iter().flat_map(|v| v.iter())))
     }};
 }
 
@@ -0,0 +1,48 @@
+/// The kind of text being written to an [`OutputSink`], allowing the sink to decide where (and in
+/// which color) each line should be displayed
+///
+/// [`OutputSink`]: ./trait.OutputSink.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OutputKind {
+    /// Regular output such as help and version messages, normally written to `stdout`
+    Info,
+    /// Error messages, normally written to `stderr`
+    Error,
+    /// Prompts and other notices which aren't errors, normally written to `stderr`
+    Warning,
+}
+
+/// A destination for all of the help, version and error text `clap` would otherwise print to
+/// `stdout` or `stderr`. This is useful when the "terminal" is something else entirely, such as
+/// the ClassiCube chat.
+///
+/// Each line of output is passed separately and without the trailing newline.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, OutputKind, OutputSink};
+/// struct Chat;
+///
+/// impl OutputSink for Chat {
+///     fn write_line(&self, text: &str, kind: OutputKind) {
+///         match kind {
+///             OutputKind::Info => println!("{}", text),
+///             OutputKind::Error | OutputKind::Warning => eprintln!("{}", text),
+///         }
+///     }
+/// }
+///
+/// let app = App::new("myprog")
+///     .output_sink(Chat);
+/// ```
+pub trait OutputSink {
+    /// Writes a single line of output
+    fn write_line(&self, text: &str, kind: OutputKind);
+}
+
+pub fn write_lines(sink: &OutputSink, text: &str, kind: OutputKind) {
+    for line in text.lines() {
+        sink.write_line(line, kind);
+    }
+}
@@ -0,0 +1,25 @@
+//! The types most applications need, re-exported so that a single glob import brings them all
+//! into scope.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use clap::prelude::*;
+//!
+//! let m = App::new("myprog")
+//!     .setting(AppSettings::SubcommandRequiredElseHelp)
+//!     .arg(Arg::with_name("verbose").short("v"))
+//!     .subcommand(SubCommand::with_name("spawn"))
+//!     .get_matches_from(vec!["myprog", "-v", "spawn"]);
+//!
+//! fn run(m: &ArgMatches) -> Result<(), Error> {
+//!     match m.subcommand_name() {
+//!         Some("spawn") => Ok(()),
+//!         _ => Err(Error::with_description("unknown command", ErrorKind::InvalidSubcommand)),
+//!     }
+//! }
+//! assert!(run(&m).is_ok());
+//! ```
+
+pub use {App, AppSettings, Arg, ArgGroup, ArgMatches, ArgSettings, Error, ErrorKind, OutputKind,
+         OutputSink, Shell, SubCommand};
@@ -4,7 +4,7 @@ use app::App;
 use strsim;
 
 // Internal
-use fmt::Format;
+use fmt::{Colorizer, ColorizerOption, Colors};
 
 /// Produces a string from a given list of possible values which is similar to
 /// the passed in value `v` with a certain confidence.
@@ -47,16 +47,24 @@ pub fn did_you_mean_flag_suffix<'z, T, I>(
     args_rest: &'z [&str],
     longs: I,
     subcommands: &'z [App],
+    color: Colors,
 ) -> (String, Option<&'z str>)
 where
     T: AsRef<str> + 'z,
     I: IntoIterator<Item = &'z T>,
 {
+    let c = Colorizer::with_palette(
+        ColorizerOption {
+            use_stderr: true,
+            when: color.when,
+        },
+        color.palette,
+    );
     if let Some(candidate) = did_you_mean(arg, longs) {
         let suffix = format!(
             "\n\tDid you mean {}{}?",
-            Format::Good("--"),
-            Format::Good(candidate)
+            c.good("--"),
+            c.good(candidate)
             );
         return (suffix, Some(candidate));
     }
@@ -82,9 +90,9 @@ where
 
             let suffix = format!(
                 "\n\tDid you mean to put '{}{}' after the subcommand '{}'?",
-                Format::Good("--"),
-                Format::Good(candidate),
-                Format::Good(subcommand.get_name())
+                c.good("--"),
+                c.good(candidate),
+                c.good(subcommand.get_name())
             );
 
             Some((score, (suffix, Some(candidate))))
@@ -95,14 +103,25 @@ where
 }
 
 /// Returns a suffix that can be empty, or is the standard 'did you mean' phrase
-pub fn did_you_mean_value_suffix<'z, T, I>(arg: &str, values: I) -> (String, Option<&'z str>)
+pub fn did_you_mean_value_suffix<'z, T, I>(
+    arg: &str,
+    values: I,
+    color: Colors,
+) -> (String, Option<&'z str>)
 where
     T: AsRef<str> + 'z,
     I: IntoIterator<Item = &'z T>,
 {
+    let c = Colorizer::with_palette(
+        ColorizerOption {
+            use_stderr: true,
+            when: color.when,
+        },
+        color.palette,
+    );
     match did_you_mean(arg, values) {
         Some(candidate) => {
-            let suffix = format!("\n\tDid you mean '{}'?", Format::Good(candidate));
+            let suffix = format!("\n\tDid you mean '{}'?", c.good(candidate));
             (suffix, Some(candidate))
         }
         None => (String::new(), None),
@@ -59,13 +59,6 @@ impl<'a> UsageParser<'a> {
                 break;
             }
         }
-        debug_assert!(
-            !arg.b.name.is_empty(),
-            format!(
-                "No name found for Arg when parsing usage string: {}",
-                self.usage
-            )
-        );
         arg.v.num_vals = match arg.v.val_names {
             Some(ref v) if v.len() >= 2 => Some(v.len() as u64),
             _ => None,
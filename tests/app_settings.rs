@@ -1,7 +1,9 @@
 extern crate clap;
 extern crate regex;
 
-use clap::{App, Arg, SubCommand, AppSettings, ErrorKind};
+use std::{env, fs, process};
+
+use clap::{App, Arg, SubCommand, AppSettings, ErrorKind, ParseLimits};
 
 include!("../clap-test.rs");
 
@@ -108,6 +110,24 @@ fn sub_command_negate_required_2() {
     assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
 }
 
+#[test]
+fn sub_command_negate_required_opt() {
+    let app = App::new("sub_command_negate")
+        .setting(AppSettings::SubcommandsNegateReqs)
+        .arg(Arg::with_name("config")
+               .long("config")
+               .takes_value(true)
+               .required(true))
+        .subcommand(SubCommand::with_name("completions"));
+
+    let m = app.clone().get_matches_from_safe(vec!["myprog", "completions"]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    assert!(m.unwrap().is_present("completions"));
+
+    let err = app.get_matches_from_safe(vec!["myprog"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+}
+
 #[test]
 fn sub_command_required() {
     let result = App::new("sc_required")
@@ -119,6 +139,25 @@ fn sub_command_required() {
     assert_eq!(err.kind, ErrorKind::MissingSubcommand);
 }
 
+#[test]
+fn sub_command_required_via_settings() {
+    let app = App::new("sc_required")
+        .settings(&[AppSettings::SubcommandRequired, AppSettings::ColorNever])
+        .arg(Arg::with_name("verbose").short("v"))
+        .subcommand(SubCommand::with_name("sub1"));
+
+    let err = app.clone().get_matches_from_safe(vec!["sc_required", "-v"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingSubcommand);
+    assert!(
+        err.message.contains("'sc_required' requires a subcommand, but one was not provided"),
+        "{}",
+        err.message
+    );
+
+    let m = app.get_matches_from(vec!["sc_required", "-v", "sub1"]);
+    assert_eq!(m.subcommand_name(), Some("sub1"));
+}
+
 #[test]
 fn arg_required_else_help() {
     let result = App::new("arg_required")
@@ -322,6 +361,27 @@ fn global_setting() {
                .is_set(AppSettings::ColoredHelp));
 }
 
+#[test]
+fn global_setting_nested_local_setting_not_propagated() {
+    let mut app = App::new("test")
+        .setting(AppSettings::ArgRequiredElseHelp)
+        .global_setting(AppSettings::ColoredHelp)
+        .arg(Arg::with_name("input"))
+        .subcommand(SubCommand::with_name("sub1")
+            .arg(Arg::with_name("input"))
+            .subcommand(SubCommand::with_name("sub2")));
+    app.p.propagate_settings();
+    let sub1 = &app.p.subcommands[0];
+    let sub2 = &sub1.p.subcommands[0];
+    assert!(sub1.p.is_set(AppSettings::ColoredHelp));
+    assert!(sub2.p.is_set(AppSettings::ColoredHelp));
+    assert!(!sub1.p.is_set(AppSettings::ArgRequiredElseHelp));
+    assert!(!sub2.p.is_set(AppSettings::ArgRequiredElseHelp));
+
+    let m = app.get_matches_from_safe(vec!["test", "sub1"]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+}
+
 #[test]
 fn global_settings() {
     let mut app = App::new("test")
@@ -458,6 +518,27 @@ fn leading_hyphen_long() {
     assert_eq!(m.value_of("some").unwrap(), "--bar");
 }
 
+#[test]
+fn leading_hyphen_opt_value_matches_flag() {
+    let app = || {
+        App::new("leadhy")
+            .arg(Arg::with_name("pattern")
+                .takes_value(true)
+                .long("pattern"))
+            .arg(Arg::with_name("exact")
+                .short("x"))
+    };
+
+    let m = app()
+        .setting(AppSettings::AllowLeadingHyphen)
+        .get_matches_from(vec!["leadhy", "--pattern", "-x"]);
+    assert_eq!(m.value_of("pattern"), Some("-x"));
+    assert!(!m.is_present("exact"));
+
+    let err = app().get_matches_from_safe(vec!["leadhy", "--pattern", "-x"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::EmptyValue);
+}
+
 #[test]
 fn leading_hyphen_opt() {
     let res = App::new("leadhy")
@@ -490,6 +571,31 @@ fn allow_negative_numbers() {
     assert_eq!(m.value_of("onum").unwrap(), "-1.2");
 }
 
+#[test]
+fn allow_negative_numbers_forms() {
+    let app = || {
+        App::new("negnum")
+            .setting(AppSettings::AllowNegativeNumbers)
+            .arg(Arg::with_name("x"))
+            .arg(Arg::with_name("y"))
+            .arg(Arg::with_name("scale")
+                .short("s")
+                .takes_value(true))
+            .arg(Arg::with_name("verbose").short("v"))
+    };
+
+    let m = app().get_matches_from(vec!["negnum", "-3", "-v", "-0.5", "-s", "-1e5"]);
+    assert_eq!(m.value_of("x"), Some("-3"));
+    assert_eq!(m.value_of("y"), Some("-0.5"));
+    assert_eq!(m.value_of("scale"), Some("-1e5"));
+    assert!(m.is_present("verbose"));
+
+    let res = App::new("negnum")
+        .arg(Arg::with_name("x"))
+        .get_matches_from_safe(vec!["negnum", "-3"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
+
 #[test]
 fn allow_negative_numbers_fail() {
     let res = App::new("negnum")
@@ -548,6 +654,117 @@ fn disable_help_subcommand() {
     assert_eq!(err.kind, ErrorKind::UnknownArgument);
 }
 
+#[test]
+fn disable_help_subcommand_user_help_dispatches() {
+    let app = App::new("prog")
+        .disable_help_subcommand(true)
+        .subcommand(SubCommand::with_name("build"))
+        .subcommand(SubCommand::with_name("help")
+            .about("shows the rules")
+            .arg(Arg::with_name("topic")));
+
+    let m = app.clone().get_matches_from(vec!["prog", "help", "build"]);
+    assert_eq!(m.subcommand_name(), Some("help"));
+    assert_eq!(m.subcommand_matches("help").unwrap().value_of("topic"), Some("build"));
+
+    let m = app.clone().get_matches_from(vec!["prog", "help"]);
+    assert_eq!(m.subcommand_name(), Some("help"));
+    assert!(!m.subcommand_matches("help").unwrap().is_present("topic"));
+
+    let err = App::new("prog")
+        .disable_help_subcommand(true)
+        .subcommand(SubCommand::with_name("build"))
+        .get_matches_from_safe(vec!["prog", "help", "build"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownArgument);
+}
+
+#[test]
+fn help_subcommand_added_once() {
+    let mut app = App::new("prog")
+        .setting(AppSettings::ColorNever)
+        .subcommand(SubCommand::with_name("build").about("builds things"));
+    assert!(app.get_matches_from_safe_borrow(vec!["prog", "build"]).is_ok());
+    assert!(app.get_matches_from_safe_borrow(vec!["prog", "build"]).is_ok());
+
+    assert_eq!(app.p.subcommands.iter().filter(|s| s.p.meta.name == "help").count(), 1);
+    let help = app.gen_help();
+    assert_eq!(help.matches("    help ").count(), 1, "{}", help);
+    let err = app.get_matches_from_safe_borrow(vec!["prog", "help", "build"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.contains("builds things"), "{}", err.message);
+}
+
+#[test]
+fn user_help_subcommand_replaces_auto_help() {
+    let m = App::new("prog")
+        .subcommand(SubCommand::with_name("build"))
+        .subcommand(SubCommand::with_name("help")
+            .arg(Arg::with_name("topic")))
+        .get_matches_from(vec!["prog", "help", "rules"]);
+
+    assert_eq!(m.subcommand_name(), Some("help"));
+    assert_eq!(m.subcommand_matches("help").unwrap().value_of("topic"), Some("rules"));
+}
+
+#[test]
+fn version_subcommand() {
+    let app = App::new("prog")
+        .version("1.0")
+        .setting(AppSettings::ColorNever)
+        .setting(AppSettings::VersionSubcommand)
+        .subcommand(SubCommand::with_name("build"));
+
+    let err = app.clone().get_matches_from_safe(vec!["prog", "version"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::VersionDisplayed);
+    let help = app.clone().gen_help();
+    assert!(help.contains("\n    version    Prints version information"), "{}", help);
+    assert_eq!(app.clone().get_matches_from(vec!["prog", "build"]).subcommand_name(), Some("build"));
+
+    // not added unless asked for
+    let err = App::new("prog")
+        .version("1.0")
+        .subcommand(SubCommand::with_name("build"))
+        .get_matches_from_safe(vec!["prog", "version"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownArgument);
+
+    // works without other subcommands, and without adding a help subcommand
+    let mut app = App::new("prog").version("1.0").setting(AppSettings::VersionSubcommand);
+    for _ in 0..2 {
+        let err = app.get_matches_from_safe_borrow(vec!["prog", "version"]).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::VersionDisplayed);
+    }
+    assert_eq!(app.p.subcommands.len(), 1);
+
+    let help = App::new("prog")
+        .version("1.0")
+        .setting(AppSettings::ColorNever)
+        .setting(AppSettings::HiddenVersionSubcommand)
+        .subcommand(SubCommand::with_name("build"))
+        .gen_help();
+    assert!(!help.contains("\n    version "), "{}", help);
+    assert!(help.contains("\n    build"), "{}", help);
+}
+
+#[test]
+fn version_subcommand_user_defined_wins() {
+    let m = App::new("prog")
+        .version("1.0")
+        .setting(AppSettings::VersionSubcommand)
+        .subcommand(SubCommand::with_name("version").arg(Arg::with_name("short").long("short")))
+        .get_matches_from(vec!["prog", "version", "--short"]);
+    assert!(m.subcommand_matches("version").unwrap().is_present("short"));
+
+    let err = App::new("prog")
+        .version("1.0")
+        .setting(AppSettings::VersionSubcommand)
+        .setting(AppSettings::DisableVersion)
+        .get_matches_from_safe(vec!["prog", "version"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownArgument);
+}
+
 #[test]
 fn dont_collapse_args() {
     let app = App::new("clap-test")
@@ -595,6 +812,22 @@ fn args_negate_subcommands_one_level() {
     assert_eq!(m.value_of("arg2"), Some("sub1"));
 }
 
+#[test]
+fn args_negate_subcommands_satisfies_sc_required() {
+    let app = App::new("disablehelp")
+        .setting(AppSettings::ArgsNegateSubcommands)
+        .setting(AppSettings::SubcommandRequired)
+        .arg_from_usage("-o, --opt [opt] 'some option'")
+        .subcommand(SubCommand::with_name("sub1"));
+
+    let res = app.clone().get_matches_from_safe(vec!["", "--opt", "val"]);
+    assert!(res.is_ok(), "error: {:?}", res.unwrap_err().kind);
+    assert_eq!(res.unwrap().value_of("opt"), Some("val"));
+
+    let res = app.get_matches_from_safe(vec![""]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::MissingSubcommand);
+}
+
 #[test]
 fn args_negate_subcommands_two_levels() {
     let res = App::new("disablehelp")
@@ -805,6 +1038,31 @@ fn external_subcommand_looks_like_built_in() {
     }
 }
 
+#[test]
+fn external_subcommand_keeps_trailing_args() {
+    let m = App::new("cc")
+        .setting(AppSettings::AllowExternalSubcommands)
+        .arg(Arg::with_name("verbose").short("v"))
+        .subcommand(SubCommand::with_name("build"))
+        .get_matches_from(vec!["cc", "-v", "plugin", "--world", "main", "-x", "--", "rest"]);
+
+    assert!(m.is_present("verbose"));
+    assert_eq!(m.subcommand_name(), Some("plugin"));
+    let sub_m = m.subcommand_matches("plugin").unwrap();
+    assert_eq!(
+        sub_m.values_of("").unwrap().collect::<Vec<_>>(),
+        ["--world", "main", "-x", "--", "rest"]
+    );
+    assert!(!sub_m.is_present("verbose"));
+
+    let err = App::new("cc")
+        .arg(Arg::with_name("verbose").short("v"))
+        .subcommand(SubCommand::with_name("build"))
+        .get_matches_from_safe(vec!["cc", "plugin"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownArgument);
+}
+
 #[test]
 fn aaos_flags() {
     // flags
@@ -963,3 +1221,383 @@ fn aaos_option_use_delim_false() {
     assert_eq!(m.occurrences_of("opt"), 1);
     assert_eq!(m.values_of("opt").unwrap().collect::<Vec<_>>(), &["one,two"]);
 }
+
+#[test]
+fn no_auto_help_version() {
+    let app = App::new("prog")
+        .version("1.0")
+        .no_auto_help_version()
+        .arg(Arg::with_name("help").short("h").long("help").takes_value(true))
+        .arg(Arg::with_name("verbose").short("V").long("version"))
+        .subcommand(SubCommand::with_name("sub").version("2.0"));
+
+    let m = app.clone()
+        .get_matches_from_safe(vec!["prog", "--help", "topic", "-V"])
+        .unwrap();
+    assert_eq!(m.value_of("help"), Some("topic"));
+    assert!(m.is_present("verbose"));
+
+    let help = app.gen_help();
+    assert!(!help.contains("Prints help information"), "{}", help);
+    assert!(!help.contains("Prints version information"), "{}", help);
+    assert!(!help.contains("Prints this message or the help"), "{}", help);
+
+    for args in &[vec!["prog", "help"], vec!["prog", "sub", "--help"], vec!["prog", "sub", "-V"]] {
+        let err = app.clone().get_matches_from_safe(args.clone()).unwrap_err();
+        assert!(
+            err.kind == ErrorKind::UnknownArgument || err.kind == ErrorKind::UnrecognizedSubcommand,
+            "{:?}: {:?}",
+            args,
+            err.kind
+        );
+    }
+}
+
+#[test]
+fn disable_help_and_version_flags() {
+    let app = App::new("prog")
+        .version("1.0")
+        .help_short("x")
+        .disable_help_flag(true)
+        .disable_version_flag(true)
+        .arg(Arg::with_name("host").short("h").takes_value(true))
+        .arg(Arg::with_name("exclude").short("x"))
+        .arg(Arg::with_name("verbose").short("V"))
+        .subcommand(SubCommand::with_name("sub"));
+
+    let m = app.clone()
+        .get_matches_from_safe(vec!["prog", "-h", "localhost", "-xV"])
+        .unwrap();
+    assert_eq!(m.value_of("host"), Some("localhost"));
+    assert!(m.is_present("exclude"));
+    assert!(m.is_present("verbose"));
+
+    for args in &[vec!["prog", "--help"], vec!["prog", "--version"]] {
+        let err = app.clone().get_matches_from_safe(args.clone()).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::UnknownArgument, "{:?}", args);
+    }
+
+    // only this command is affected, the help subcommand and subcommand flags remain
+    let err = app.clone().get_matches_from_safe(vec!["prog", "sub", "--help"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    let err = app.clone().get_matches_from_safe(vec!["prog", "help"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+
+    let help = App::new("prog").disable_help_flag(true).disable_help_flag(false).gen_help();
+    assert!(help.contains("Prints help information"), "{}", help);
+}
+
+#[test]
+fn bin_name_strips_exe_extension() {
+    let usage = |argv0| {
+        App::new("prog")
+            .arg(Arg::with_name("input"))
+            .get_matches_from(vec![argv0])
+            .usage()
+            .to_owned()
+    };
+
+    assert_eq!(usage("target/release/myapp.exe"), "USAGE:\n    myapp [input]");
+    assert_eq!(usage("MYAPP.EXE"), "USAGE:\n    MYAPP [input]");
+    assert_eq!(usage("./my.app"), "USAGE:\n    my.app [input]");
+    assert_eq!(usage("exe"), "USAGE:\n    exe [input]");
+}
+
+#[test]
+fn nested_subcommand_usage_includes_parent_bin_names() {
+    let app = || {
+        App::new("prog")
+            .subcommand(SubCommand::with_name("child")
+                .arg(Arg::with_name("quiet").short("q"))
+                .subcommand(SubCommand::with_name("grandchild")
+                    .arg(Arg::with_name("target").required(true))))
+    };
+
+    let m = app().get_matches_from(vec!["target/debug/prog", "child", "-q", "grandchild", "x"]);
+    let child = m.subcommand_matches("child").unwrap();
+    assert_eq!(child.usage(), "USAGE:\n    prog child [FLAGS] [SUBCOMMAND]");
+    let grandchild = child.subcommand_matches("grandchild").unwrap();
+    assert_eq!(grandchild.usage(), "USAGE:\n    prog child grandchild <target>");
+
+    let m = app().bin_name("mytool").get_matches_from(vec!["prog", "child", "grandchild", "x"]);
+    let grandchild = m.subcommand_matches("child").unwrap().subcommand_matches("grandchild").unwrap();
+    assert_eq!(grandchild.usage(), "USAGE:\n    mytool child grandchild <target>");
+}
+
+#[test]
+fn error_exit_code() {
+    // Exiting can only be observed from outside, so this test runs itself in a child process
+    if let Ok(args) = env::var("CLAP_TEST_EXIT_ARGS") {
+        let mut app = App::new("prog").arg(Arg::with_name("input").required(true));
+        if let Ok(code) = env::var("CLAP_TEST_EXIT_CODE") {
+            app = app.error_exit_code(code.parse().unwrap());
+        }
+        app.get_matches_from(args.split(' '));
+        process::exit(0);
+    }
+
+    let status = |args: &str, code: Option<&str>| {
+        let mut cmd = process::Command::new(env::current_exe().unwrap());
+        cmd.args(&["error_exit_code", "--exact", "--quiet"])
+            .env("CLAP_TEST_EXIT_ARGS", args);
+        if let Some(code) = code {
+            cmd.env("CLAP_TEST_EXIT_CODE", code);
+        }
+        cmd.output().unwrap().status.code()
+    };
+
+    assert_eq!(status("prog", None), Some(1));
+    assert_eq!(status("prog", Some("2")), Some(2));
+    assert_eq!(status("prog --nope in", Some("64")), Some(64));
+    assert_eq!(status("prog --help", Some("2")), Some(0));
+    assert_eq!(status("prog in", Some("2")), Some(0));
+}
+
+#[test]
+fn response_files() {
+    let dir = env::temp_dir().join(format!("clap-response-files-{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let outer = dir.join("outer.txt");
+    let inner = dir.join("inner.txt");
+    let looping = dir.join("loop.txt");
+    fs::write(&outer, format!("--world main\n@{}  first", inner.display())).unwrap();
+    fs::write(&inner, "-v\t-v -- @literal").unwrap();
+    fs::write(&looping, format!("@{}", looping.display())).unwrap();
+    let loop_a = dir.join("loop_a.txt");
+    let loop_b = dir.join("loop_b.txt");
+    fs::write(&loop_a, format!("-v @{}", loop_b.display())).unwrap();
+    fs::write(&loop_b, format!("-v @{}", loop_a.display())).unwrap();
+    let arg = |p: &::std::path::Path| format!("@{}", p.display());
+
+    let app = || {
+        App::new("prog")
+            .setting(AppSettings::AllowResponseFiles)
+            .setting(AppSettings::ColorNever)
+            .arg(Arg::with_name("world").long("world").takes_value(true))
+            .arg(Arg::with_name("verbose").short("v").multiple(true))
+            .arg(Arg::with_name("players").multiple(true))
+    };
+
+    let m = app().get_matches_from(vec!["prog".to_owned(), arg(&outer), "second".to_owned()]);
+    assert_eq!(m.value_of("world"), Some("main"));
+    assert_eq!(m.occurrences_of("verbose"), 2);
+    assert_eq!(
+        m.values_of("players").unwrap().collect::<Vec<_>>(),
+        ["@literal", "first", "second"]
+    );
+
+    let err = app()
+        .get_matches_from_safe(vec!["prog".to_owned(), arg(&dir.join("missing.txt"))])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Io);
+    assert!(err.message.contains("Couldn't read the response file"), "{}", err.message);
+
+    let err = app().get_matches_from_safe(vec!["prog".to_owned(), arg(&looping)]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Io);
+    assert!(err.message.contains("it includes itself"), "{}", err.message);
+
+    let err = app().get_matches_from_safe(vec!["prog".to_owned(), arg(&loop_a)]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Io);
+    assert!(err.message.contains("it includes itself"), "{}", err.message);
+
+    let m = App::new("prog")
+        .arg(Arg::with_name("players").multiple(true))
+        .get_matches_from(vec!["prog".to_owned(), arg(&inner)]);
+    assert_eq!(m.values_of("players").unwrap().collect::<Vec<_>>(), [&*arg(&inner)]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn response_files_bounded() {
+    let dir = env::temp_dir().join(format!("clap-response-files-bounded-{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let arg = |p: &::std::path::Path| format!("@{}", p.display());
+    let app = || {
+        App::new("prog")
+            .setting(AppSettings::AllowResponseFiles)
+            .setting(AppSettings::ColorNever)
+            .arg(Arg::with_name("players").multiple(true))
+    };
+
+    // Would expand to a million arguments
+    let (a, b, c) = (dir.join("a.txt"), dir.join("b.txt"), dir.join("c.txt"));
+    fs::write(&a, vec![arg(&b); 1000].join(" ")).unwrap();
+    fs::write(&b, vec![arg(&c); 1000].join(" ")).unwrap();
+    fs::write(&c, "x").unwrap();
+    let err = app().get_matches_from_safe(vec!["prog".to_owned(), arg(&a)]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::LimitExceeded);
+    assert_eq!(err.info, Some(vec!["arguments".to_owned(), "65536".to_owned()]));
+
+    let err = app()
+        .parse_limits(ParseLimits::new().max_tokens(100))
+        .get_matches_from_safe(vec!["prog".to_owned(), arg(&a)])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::LimitExceeded);
+    assert_eq!(err.info, Some(vec!["arguments".to_owned(), "100".to_owned()]));
+
+    let deep = (0..20).map(|i| dir.join(format!("deep{}.txt", i))).collect::<Vec<_>>();
+    for w in deep.windows(2) {
+        fs::write(&w[0], arg(&w[1])).unwrap();
+    }
+    fs::write(&deep[19], "x").unwrap();
+    let err = app().get_matches_from_safe(vec!["prog".to_owned(), arg(&deep[0])]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Io);
+    assert!(err.message.contains("nested more than 16"), "{}", err.message);
+    let m = app().get_matches_from(vec!["prog".to_owned(), arg(&deep[4])]);
+    assert_eq!(m.value_of("players"), Some("x"));
+
+    let big = dir.join("big.txt");
+    fs::write(&big, "x ".repeat(1 << 20)).unwrap();
+    let err = app().get_matches_from_safe(vec!["prog".to_owned(), arg(&big)]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Io);
+    assert!(err.message.contains("larger than"), "{}", err.message);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn replace_after_response_files() {
+    let dir = env::temp_dir().join(format!("clap-replace-{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let spawn = dir.join("spawn.txt");
+    fs::write(&spawn, "--home").unwrap();
+    let at_spawn = format!("@{}", spawn.display());
+
+    let app = |target: &[&str]| {
+        App::new("prog")
+            .setting(AppSettings::AllowResponseFiles)
+            .replace("--home", &["--world", "main"])
+            .replace("again", target)
+            .arg(Arg::with_name("world").long("world").takes_value(true))
+            .arg(Arg::with_name("players").multiple(true))
+            .get_matches_from(vec!["prog", "again", &*at_spawn, "--", "--home"])
+    };
+
+    // The response file is expanded before replacing, so its `--home` is replaced
+    let m = app(&["Notch"]);
+    assert_eq!(m.value_of("world"), Some("main"));
+    assert_eq!(m.values_of("players").unwrap().collect::<Vec<_>>(), ["Notch", "--home"]);
+
+    // Replacements aren't expanded or replaced again, so this can't loop
+    let m = app(&[&*at_spawn, "again"]);
+    assert_eq!(m.value_of("world"), Some("main"));
+    assert_eq!(
+        m.values_of("players").unwrap().collect::<Vec<_>>(),
+        [&*at_spawn, "again", "--home"]
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn args_from_usage_skips_blank_lines() {
+    let m = App::new("prog")
+        .args_from_usage(
+            "
+            -c, --config=[FILE] 'Sets a config file'
+
+               -v... 'Verbosity'
+            <input> 'The input file'
+            ",
+        )
+        .get_matches_from(vec!["prog", "-vv", "in.txt"]);
+    assert_eq!(m.occurrences_of("v"), 2);
+    assert_eq!(m.value_of("input"), Some("in.txt"));
+    assert!(!m.is_present("config"));
+}
+
+#[test]
+#[should_panic(expected = "line 3 of usage string: 'no name here'")]
+fn args_from_usage_names_malformed_line() {
+    App::new("prog").args_from_usage(
+        "-c, --config=[FILE] 'Sets a config file'
+         <input> 'The input file'
+         'no name here'",
+    );
+}
+
+#[test]
+fn normalize_long_separators() {
+    let app = || {
+        App::new("prog")
+            .setting(AppSettings::NormalizeLongSeparators)
+            .arg(Arg::with_name("dry").long("dry-run"))
+            .arg(Arg::with_name("out").long("out_dir").takes_value(true))
+            .arg(Arg::with_name("lvl").long("level").alias("log_level").takes_value(true))
+    };
+
+    for args in &[
+        ["prog", "--dry-run", "--out_dir=a", "--log_level", "3"],
+        ["prog", "--dry_run", "--out-dir=a", "--log-level", "3"],
+    ] {
+        let m = app().get_matches_from(args.iter());
+        assert!(m.is_present("dry"));
+        assert_eq!(m.value_of("out"), Some("a"));
+        assert_eq!(m.value_of("lvl"), Some("3"));
+    }
+
+    let res = App::new("prog")
+        .arg(Arg::with_name("dry").long("dry-run"))
+        .get_matches_from_safe(vec!["prog", "--dry_run"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
+
+#[test]
+fn cloned_app_variants_are_independent() {
+    let base = App::new("prog")
+        .arg(Arg::with_name("verbose").short("v"))
+        .subcommand(SubCommand::with_name("build").arg(Arg::with_name("release").long("release")));
+
+    let strict = base.clone()
+        .setting(AppSettings::SubcommandRequired)
+        .subcommand(SubCommand::with_name("run"));
+
+    let m = base.clone().get_matches_from(vec!["prog", "-v"]);
+    assert!(m.is_present("verbose"));
+    assert_eq!(
+        base.clone().get_matches_from_safe(vec!["prog", "run"]).unwrap_err().kind,
+        ErrorKind::UnknownArgument
+    );
+
+    let m = strict.clone().get_matches_from(vec!["prog", "run"]);
+    assert_eq!(m.subcommand_name(), Some("run"));
+    assert_eq!(
+        strict.clone().get_matches_from_safe(vec!["prog", "-v"]).unwrap_err().kind,
+        ErrorKind::MissingSubcommand
+    );
+
+    for app in &[base, strict] {
+        let m = app.clone().get_matches_from(vec!["prog", "build", "--release"]);
+        assert!(m.subcommand_matches("build").unwrap().is_present("release"));
+    }
+}
+
+#[test]
+fn app_getters() {
+    let app = App::new("prog")
+        .version("1.2")
+        .author("Someone <someone@example.com>")
+        .about("Does things")
+        .arg(Arg::with_name("verbose").short("v"))
+        .arg(Arg::with_name("input"))
+        .arg(Arg::with_name("config").long("config").takes_value(true))
+        .arg(Arg::with_name("quiet").short("q"))
+        .arg(Arg::with_name("output"));
+
+    assert_eq!(app.get_name(), "prog");
+    assert_eq!(app.get_version(), Some("1.2"));
+    assert_eq!(app.get_author(), Some("Someone <someone@example.com>"));
+    assert_eq!(app.get_about(), Some("Does things"));
+    assert_eq!(app.get_flag_names(), ["verbose", "quiet"]);
+    assert_eq!(app.get_opt_names(), ["config"]);
+    assert_eq!(app.get_positional_names(), ["input", "output"]);
+
+    let bare = App::new("bare");
+    assert_eq!(bare.get_version(), None);
+    assert_eq!(bare.get_about(), None);
+    assert!(bare.get_flag_names().is_empty());
+}
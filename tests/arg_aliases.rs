@@ -198,3 +198,21 @@ fn visible_arg_aliases_help_output() {
                 .visible_aliases(&["v_flg", "flag2", "flg3"])));
     assert!(test::compare_output(app, "ct test --help", SC_VISIBLE_ALIAS_HELP, false));
 }
+
+#[test]
+fn alias_of_option_with_equals_hidden_in_help() {
+    let app = App::new("alias")
+        .arg(Arg::with_name("color")
+            .long("color")
+            .takes_value(true)
+            .help("when to use colors")
+            .alias("colour"));
+
+    let help = app.gen_help();
+    assert!(help.contains("--color <color>"));
+    assert!(!help.contains("colour"));
+
+    let m = app.get_matches_from_safe(vec!["", "--colour=never"]);
+    assert!(m.is_ok());
+    assert_eq!(m.unwrap().value_of("color"), Some("never"));
+}
@@ -0,0 +1,256 @@
+#[macro_use]
+extern crate clap;
+
+use clap::{App, Arg, ArgGroup, ErrorKind, SubCommand};
+
+#[test]
+fn debug_output_groups_args() {
+    let m = App::new("myprog")
+        .arg(Arg::with_name("verbose").short("v").multiple(true))
+        .arg(Arg::with_name("config").short("c").takes_value(true))
+        .arg(Arg::with_name("input"))
+        .subcommand(SubCommand::with_name("test").arg(Arg::with_name("opt").long("opt").takes_value(true)))
+        .get_matches_from(vec!["myprog", "-vvv", "-c", "a.toml", "in.txt", "test", "--opt", "val"]);
+
+    assert_eq!(
+        format!("{:?}", m),
+        "ArgMatches {
+    flags: { verbose\u{d7}3 }
+    options: { config: [\"a.toml\"] }
+    positionals: { input: [\"in.txt\"] }
+    subcommand: test ArgMatches {
+        flags: { }
+        options: { opt: [\"val\"] }
+        positionals: { }
+    }
+}"
+    );
+}
+
+#[test]
+fn debug_output_separates_kinds() {
+    let m = App::new("myprog")
+        .arg(Arg::with_name("quiet").short("q"))
+        .arg(Arg::with_name("debug").long("debug").multiple(true))
+        .arg(Arg::with_name("level").long("level").takes_value(true).min_values(0))
+        .arg(Arg::with_name("world").long("world").takes_value(true).default_value("main"))
+        .arg(Arg::with_name("target"))
+        .arg(Arg::with_name("players").multiple(true))
+        .get_matches_from(vec!["myprog", "-q", "--debug", "spawn", "--debug", "Notch", "jeb_", "--level"]);
+    let out = format!("{:?}", m);
+    let group = |name: &str| {
+        out.lines()
+            .find(|l| l.trim_start().starts_with(name))
+            .unwrap_or_else(|| panic!("no {} group in {}", name, out))
+            .trim()
+            .to_owned()
+    };
+
+    assert_eq!(group("flags:"), "flags: { debug\u{d7}2, quiet\u{d7}1 }");
+    assert_eq!(group("options:"), "options: { level: [], world: [\"main\"] }");
+    assert_eq!(
+        group("positionals:"),
+        "positionals: { players: [\"Notch\", \"jeb_\"], target: [\"spawn\"] }"
+    );
+}
+
+#[test]
+fn subcommand_accessors() {
+    let m = App::new("myprog")
+        .subcommand(SubCommand::with_name("test").arg(Arg::with_name("opt").long("opt").takes_value(true)))
+        .subcommand(SubCommand::with_name("other"))
+        .get_matches_from(vec!["myprog", "test", "--opt", "val"]);
+
+    assert_eq!(m.subcommand_name(), Some("test"));
+    assert!(m.subcommand_matches("other").is_none());
+    assert_eq!(m.subcommand_matches("test").unwrap().value_of("opt"), Some("val"));
+    match m.subcommand() {
+        ("test", Some(sub_m)) => assert_eq!(sub_m.value_of("opt"), Some("val")),
+        _ => unreachable!(),
+    }
+
+    let m = App::new("myprog")
+        .subcommand(SubCommand::with_name("test"))
+        .get_matches_from(vec!["myprog"]);
+    assert_eq!(m.subcommand_name(), None);
+    let (name, sub_m) = m.subcommand();
+    assert_eq!(name, "");
+    assert!(sub_m.is_none());
+}
+
+#[test]
+fn is_present_any_and_all() {
+    let m = App::new("myprog")
+        .arg(Arg::with_name("debug").short("d"))
+        .arg(Arg::with_name("verbose").short("v"))
+        .arg(Arg::with_name("quiet").short("q"))
+        .subcommand(SubCommand::with_name("test"))
+        .get_matches_from(vec!["myprog", "-d", "-v", "test"]);
+
+    assert!(m.is_present_any(&["quiet", "debug"]));
+    assert!(!m.is_present_any(&["quiet", "nope"]));
+    assert!(m.is_present_all(&["debug", "verbose", "test"]));
+    assert!(!m.is_present_all(&["debug", "quiet"]));
+    assert!(!m.is_present_any::<&str>(&[]));
+    assert!(m.is_present_all::<&str>(&[]));
+}
+
+#[test]
+fn args_present_counts_used_args() {
+    let app = || {
+        App::new("myprog")
+            .arg(Arg::with_name("verbose").short("v").multiple(true))
+            .arg(Arg::with_name("config").short("c").takes_value(true))
+            .arg(Arg::with_name("world").long("world").default_value("main"))
+            .arg(Arg::with_name("input"))
+            .group(ArgGroup::with_name("noise").args(&["verbose", "config"]).multiple(true))
+            .subcommand(SubCommand::with_name("test").arg(Arg::with_name("quiet").short("q")))
+    };
+
+    let m = app().get_matches_from(vec!["myprog"]);
+    assert_eq!(m.args_present(), 0);
+    assert!(m.is_present("world"));
+
+    let m = app().get_matches_from(vec!["myprog", "-vvv"]);
+    assert_eq!(m.args_present(), 1);
+    assert!(m.is_present("noise"));
+
+    let m = app().get_matches_from(vec!["myprog", "-v", "-c", "a.toml", "in.txt", "--world", "x"]);
+    assert_eq!(m.args_present(), 4);
+    assert!(m.is_present("config") && m.is_present("input"));
+
+    let m = app().get_matches_from(vec!["myprog", "test", "-q"]);
+    assert_eq!(m.args_present(), 0);
+    assert_eq!(m.subcommand_matches("test").unwrap().args_present(), 1);
+}
+
+#[test]
+fn values_of_keeps_command_line_order() {
+    let m = App::new("myprog")
+        .arg(Arg::with_name("include").short("I").takes_value(true).multiple(true))
+        .arg(Arg::with_name("verbose").short("v"))
+        .get_matches_from(vec!["myprog", "-I", "zeta", "-v", "-I", "alpha", "-Imid"]);
+
+    let vals: Vec<_> = m.values_of("include").unwrap().collect();
+    assert_eq!(vals, ["zeta", "alpha", "mid"]);
+    assert!(m.values_of("missing").is_none());
+}
+
+#[test]
+fn subcommand_matches_mut() {
+    let mut m = App::new("myprog")
+        .arg(Arg::with_name("world").long("world").takes_value(true))
+        .subcommand(SubCommand::with_name("goto")
+            .arg(Arg::with_name("world").long("world").takes_value(true))
+            .arg(Arg::with_name("quiet").short("q")))
+        .get_matches_from(vec!["myprog", "--world", "main", "goto", "-q"]);
+
+    assert!(m.subcommand_matches_mut("other").is_none());
+
+    let world = m.value_of("world").unwrap().to_owned();
+    {
+        let sub_m = m.subcommand_matches_mut("goto").unwrap();
+        sub_m.set_value("world", world);
+        sub_m.unset("quiet");
+    }
+
+    let sub_m = m.subcommand_matches("goto").unwrap();
+    assert_eq!(sub_m.value_of("world"), Some("main"));
+    assert_eq!(sub_m.occurrences_of("world"), 0);
+    assert!(!sub_m.is_present("quiet"));
+    assert_eq!(m.value_of("world"), Some("main"));
+}
+
+#[test]
+fn value_of_t_invalid_value_message() {
+    let m = App::new("myprog")
+        .arg(Arg::with_name("port")
+            .long("port")
+            .takes_value(true)
+            .invalid_value_message("port must be between 1 and 65535, not '{}'"))
+        .arg(Arg::with_name("count").long("count").takes_value(true))
+        .get_matches_from(vec!["myprog", "--port", "70000", "--count", "lots"]);
+
+    let err = m.value_of_t::<u16>("port").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueValidation);
+    assert!(err.message.contains("port must be between 1 and 65535, not '70000'"), "{}", err.message);
+    let err = value_t!(m, "port", u16).unwrap_err();
+    assert!(err.message.contains("port must be between 1 and 65535, not '70000'"), "{}", err.message);
+
+    let err = m.value_of_t::<u32>("count").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueValidation);
+    assert!(err.message.contains("The argument 'lots' isn't a valid value"), "{}", err.message);
+
+    assert_eq!(m.value_of_t::<u32>("port").unwrap(), 70000);
+    assert_eq!(value_t!(m.value_of("port"), u32).unwrap(), 70000);
+    assert_eq!(m.value_of_t::<u32>("nope").unwrap_err().kind, ErrorKind::ArgumentNotFound);
+}
+
+#[test]
+fn typed_values_of_t() {
+    let m = App::new("myprog")
+        .arg(Arg::with_name("offset").long("offset").takes_value(true).allow_hyphen_values(true))
+        .arg(Arg::with_name("scale").long("scale").takes_value(true))
+        .arg(Arg::with_name("coords").long("coords").takes_value(true).multiple(true))
+        .get_matches_from(vec!["myprog", "--offset", "-12", "--scale", "0.25", "--coords", "3", "x", "4"]);
+
+    assert_eq!(m.value_of_t::<i32>("offset").unwrap(), -12);
+    assert_eq!(m.value_of_t::<f64>("scale").unwrap(), 0.25);
+    assert_eq!(m.values_of_t::<f64>("scale").unwrap(), [0.25]);
+    assert_eq!(m.value_of_t::<i32>("scale").unwrap_err().kind, ErrorKind::ValueValidation);
+
+    let err = m.values_of_t::<i32>("coords").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueValidation);
+    assert!(err.message.contains("The argument 'x' isn't a valid value"), "{}", err.message);
+    assert!(values_t!(m, "coords", i32).is_err());
+    assert_eq!(m.values_of_t::<i32>("nope").unwrap_err().kind, ErrorKind::ArgumentNotFound);
+}
+
+#[test]
+fn eq_ignoring_value_order() {
+    let app = || {
+        App::new("myprog")
+            .arg(Arg::with_name("verbose").short("v").multiple(true))
+            .arg(Arg::with_name("include").short("I").multiple(true).number_of_values(1))
+            .subcommand(SubCommand::with_name("test")
+                .arg(Arg::with_name("quiet").short("q"))
+                .arg(Arg::with_name("opt").long("opt").takes_value(true)))
+    };
+
+    let m1 = app().get_matches_from(vec!["myprog", "-v", "-I", "a", "-v", "-I", "b", "test", "-q", "--opt", "x"]);
+    let m2 = app().get_matches_from(vec!["myprog", "-I", "a", "-vv", "-I", "b", "test", "--opt", "x", "-q"]);
+    assert!(m1.eq_ignoring_value_order(&m2));
+    assert!(m2.eq_ignoring_value_order(&m1));
+
+    let reordered = app().get_matches_from(vec!["myprog", "-vv", "-I", "b", "-I", "a", "test", "-q", "--opt", "x"]);
+    assert!(!m1.eq_ignoring_value_order(&reordered));
+    let fewer = app().get_matches_from(vec!["myprog", "-v", "-I", "a", "-I", "b", "test", "-q", "--opt", "x"]);
+    assert!(!m1.eq_ignoring_value_order(&fewer));
+    let sub_differs = app().get_matches_from(vec!["myprog", "-vv", "-I", "a", "-I", "b", "test", "--opt", "x"]);
+    assert!(!m1.eq_ignoring_value_order(&sub_differs));
+    let no_sub = app().get_matches_from(vec!["myprog", "-vv", "-I", "a", "-I", "b"]);
+    assert!(!m1.eq_ignoring_value_order(&no_sub));
+}
+
+#[test]
+fn usage_after_parse() {
+    let app = || {
+        App::new("prog")
+            .arg(Arg::with_name("verbose").short("v"))
+            .subcommand(SubCommand::with_name("build")
+                .arg(Arg::with_name("target").required(true))
+                .subcommand(SubCommand::with_name("docs").arg(Arg::with_name("open").long("open"))))
+    };
+
+    let mut out = vec![];
+    app().write_usage(&mut out).unwrap();
+    let m = app().get_matches_from(vec!["prog", "-v"]);
+    assert_eq!(format!("{}\n", m.usage()), String::from_utf8(out).unwrap());
+
+    let m = app().get_matches_from(vec!["prog", "build", "x", "docs", "--open"]);
+    assert_eq!(m.usage(), "USAGE:\n    prog [FLAGS] [SUBCOMMAND]");
+    let build_m = m.subcommand_matches("build").unwrap();
+    assert_eq!(build_m.usage(), "USAGE:\n    prog build <target> [SUBCOMMAND]");
+    let docs_m = build_m.subcommand_matches("docs").unwrap();
+    assert_eq!(docs_m.usage(), "USAGE:\n    prog build docs [FLAGS]");
+}
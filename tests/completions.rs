@@ -3,6 +3,9 @@ extern crate clap;
 
 use clap::{App, Arg, SubCommand, Shell};
 use regex::Regex;
+use std::env;
+use std::fs;
+use std::process;
 
 static BASH: &'static str = r#"_myapp() {
     local i cur prev opts cmds
@@ -169,7 +172,7 @@ static FISH: &'static str = r#"complete -c myapp -n "__fish_use_subcommand" -s h
 complete -c myapp -n "__fish_use_subcommand" -s V -l version -d 'Prints version information'
 complete -c myapp -n "__fish_use_subcommand" -f -a "test" -d 'tests things'
 complete -c myapp -n "__fish_use_subcommand" -f -a "help" -d 'Prints this message or the help of the given subcommand(s)'
-complete -c myapp -n "__fish_seen_subcommand_from test" -l case -d 'the case to test'
+complete -c myapp -n "__fish_seen_subcommand_from test" -l case -d 'the case to test' -r
 complete -c myapp -n "__fish_seen_subcommand_from test" -s h -l help -d 'Prints help information'
 complete -c myapp -n "__fish_seen_subcommand_from test" -s V -l version -d 'Prints version information'
 complete -c myapp -n "__fish_seen_subcommand_from help" -s h -l help -d 'Prints help information'
@@ -513,10 +516,10 @@ complete -c my_app -n "__fish_use_subcommand" -f -a "test" -d 'tests things'
 complete -c my_app -n "__fish_use_subcommand" -f -a "some_cmd" -d 'tests other things'
 complete -c my_app -n "__fish_use_subcommand" -f -a "some-cmd-with-hypens"
 complete -c my_app -n "__fish_use_subcommand" -f -a "help" -d 'Prints this message or the help of the given subcommand(s)'
-complete -c my_app -n "__fish_seen_subcommand_from test" -l case -d 'the case to test'
+complete -c my_app -n "__fish_seen_subcommand_from test" -l case -d 'the case to test' -r
 complete -c my_app -n "__fish_seen_subcommand_from test" -s h -l help -d 'Prints help information'
 complete -c my_app -n "__fish_seen_subcommand_from test" -s V -l version -d 'Prints version information'
-complete -c my_app -n "__fish_seen_subcommand_from some_cmd" -l config -d 'the other case to test'
+complete -c my_app -n "__fish_seen_subcommand_from some_cmd" -l config -d 'the other case to test' -r
 complete -c my_app -n "__fish_seen_subcommand_from some_cmd" -s h -l help -d 'Prints help information'
 complete -c my_app -n "__fish_seen_subcommand_from some_cmd" -s V -l version -d 'Prints version information'
 complete -c my_app -n "__fish_seen_subcommand_from some-cmd-with-hypens" -s h -l help -d 'Prints help information'
@@ -881,3 +884,139 @@ fn zsh_with_special_help() {
 
     assert!(compare(&*string, ZSH_SPECIAL_HELP));
 }
+
+#[test]
+fn gen_completions_and_manpage_to_dir() {
+    let dir = env::temp_dir().join(format!("clap-gen-to-dir-{}", process::id())).join("out");
+    let _ = fs::remove_dir_all(dir.parent().unwrap());
+
+    let mut app = build_app().bin_name("myapp");
+    app.gen_completions_to_dir(&dir, &[Shell::Bash, Shell::Zsh, Shell::Fish])
+        .unwrap();
+    app.gen_manpage_to(&dir.join("man").join("myapp.1")).unwrap();
+
+    let mut names: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["_myapp", "man", "myapp.bash", "myapp.fish"]);
+    assert!(compare(&*fs::read_to_string(dir.join("myapp.bash")).unwrap(), BASH));
+
+    let man = fs::read_to_string(dir.join("man").join("myapp.1")).unwrap();
+    assert!(man.starts_with(".TH MYAPP 1"), "{}", man);
+    assert!(man.contains(".SH SYNOPSIS\nmyapp"), "{}", man);
+
+    fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn gen_completions_bash() {
+    let app = build_app()
+        .arg(Arg::with_name("verbose").long("verbose"))
+        .arg(Arg::with_name("color")
+            .long("color")
+            .takes_value(true)
+            .possible_values(&["always", "never"]))
+        .subcommand(SubCommand::with_name("build")
+            .arg(Arg::with_name("release").long("release")));
+    let mut buf = vec![];
+    app.gen_completions_bash("myapp", &mut buf);
+    let script = String::from_utf8(buf).unwrap();
+
+    assert!(script.contains("complete -F _myapp -o bashdefault -o default myapp"), "{}", script);
+    assert!(script.contains("--verbose"), "{}", script);
+    assert!(script.contains("--color)\n                    COMPREPLY=($(compgen -W \"always never\""), "{}", script);
+    assert!(script.contains("cmd+=\"__build\""), "{}", script);
+    assert!(script.contains("cmd+=\"__test\""), "{}", script);
+    let build = script.split("myapp__build)").nth(1).unwrap();
+    assert!(build.lines().nth(1).unwrap().contains("--release"), "{}", script);
+    let test = script.split("myapp__test)").nth(1).unwrap();
+    assert!(test.lines().nth(1).unwrap().contains("--case"), "{}", script);
+
+    // the App is left untouched and can still be used afterwards
+    let m = app.get_matches_from(vec!["myapp", "--color", "never", "build", "--release"]);
+    assert_eq!(m.value_of("color"), Some("never"));
+    assert!(m.subcommand_matches("build").unwrap().is_present("release"));
+}
+
+#[test]
+fn gen_completions_zsh() {
+    let app = App::new("myapp")
+        .arg(Arg::with_name("input").required(true).help("the input file"))
+        .arg(Arg::with_name("output").help("the output file"))
+        .arg(Arg::with_name("color")
+            .long("color")
+            .takes_value(true)
+            .possible_values(&["always", "never"])
+            .help("when to use colors"))
+        .subcommand(SubCommand::with_name("build")
+            .about("builds things")
+            .arg(Arg::with_name("release").long("release").help("build in release mode")));
+    let mut buf = vec![];
+    app.gen_completions_zsh("myapp", &mut buf);
+    let script = String::from_utf8(buf).unwrap();
+
+    assert!(script.starts_with("#compdef myapp\n"), "{}", script);
+    assert!(script.contains("'--color=[when to use colors]: :(always never)' \\"), "{}", script);
+    assert!(script.contains("':input -- the input file:_files' \\"), "{}", script);
+    assert!(script.contains("'::output -- the output file:_files' \\"), "{}", script);
+    assert!(script.contains("\"build:builds things\" \\"), "{}", script);
+    assert!(script.contains("(build)\n_arguments \"${_arguments_options[@]}\" \\\n'--release[build in release mode]' \\"), "{}", script);
+    assert!(script.contains("_describe -t commands 'myapp commands' commands \"$@\""), "{}", script);
+}
+
+#[test]
+fn gen_completions_fish() {
+    let app = App::new("myapp")
+        .arg(Arg::with_name("mode").possible_values(&["fast", "slow"]).help("how to run"))
+        .arg(Arg::with_name("color")
+            .long("color")
+            .takes_value(true)
+            .possible_values(&["always", "never"])
+            .help("when to use colors"))
+        .arg(Arg::with_name("verbose").short("v").long("verbose").help("talk more"))
+        .subcommand(SubCommand::with_name("build")
+            .about("builds things")
+            .arg(Arg::with_name("jobs").short("j").takes_value(true).help("number of jobs")));
+    let mut buf = vec![];
+    app.gen_completions_fish("myapp", &mut buf);
+    let script = String::from_utf8(buf).unwrap();
+
+    let expected = [
+        "complete -c myapp -n \"__fish_use_subcommand\" -l color -d 'when to use colors' -r -f -a \"always never\"",
+        "complete -c myapp -n \"__fish_use_subcommand\" -s v -l verbose -d 'talk more'",
+        "complete -c myapp -n \"__fish_use_subcommand\" -f -a \"fast slow\" -d 'how to run'",
+        "complete -c myapp -n \"__fish_use_subcommand\" -f -a \"build\" -d 'builds things'",
+        "complete -c myapp -n \"__fish_seen_subcommand_from build\" -s j -d 'number of jobs' -r",
+    ];
+    for line in &expected {
+        assert!(script.lines().any(|l| l == *line), "missing {:?} in\n{}", line, script);
+    }
+}
+
+#[test]
+fn gen_manpage() {
+    let app = App::new("myapp")
+        .version("1.0")
+        .about("Tests man pages")
+        .arg(Arg::with_name("config")
+            .short("c")
+            .long("config")
+            .value_name("FILE")
+            .help("Sets a custom config file"))
+        .arg(Arg::with_name("secret").long("secret").hidden(true))
+        .arg(Arg::with_name("input").help(".starts with a dot"))
+        .subcommand(SubCommand::with_name("test").about("tests things"));
+    let mut buf = vec![];
+    app.gen_manpage(&mut buf).unwrap();
+    let man = String::from_utf8(buf).unwrap();
+
+    assert!(man.starts_with(".TH MYAPP 1 \"\" \"myapp 1.0\"\n.SH NAME\nmyapp \\- Tests man pages\n"), "{}", man);
+    assert!(man.contains(".SH SYNOPSIS\nmyapp [OPTIONS] [input] [SUBCOMMAND]\n"), "{}", man);
+    assert!(man.contains(".SH OPTIONS\n.TP\n\\fB\\-h\\fR, \\fB\\-\\-help\\fR\nPrints help information\n"), "{}", man);
+    assert!(man.contains(".TP\n\\fB\\-c\\fR, \\fB\\-\\-config\\fR \\fI<FILE>\\fR\nSets a custom config file\n"), "{}", man);
+    assert!(!man.contains("secret"), "{}", man);
+    assert!(man.contains(".SH ARGS\n.TP\n\\fI<input>\\fR\n\\&.starts with a dot\n"), "{}", man);
+    assert!(man.contains(".SH SUBCOMMANDS\n.TP\n\\fBtest\\fR\ntests things\n"), "{}", man);
+}
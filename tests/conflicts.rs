@@ -3,7 +3,7 @@ extern crate regex;
 
 include!("../clap-test.rs");
 
-use clap::{App, Arg, ErrorKind, ArgGroup};
+use clap::{App, AppSettings, Arg, ErrorKind, ArgGroup};
 
 static CONFLICT_ERR: &'static str = "error: The argument '-F' cannot be used with '--flag'
 
@@ -100,3 +100,87 @@ fn conflict_with_unused_default_value() {
     assert_eq!(m.value_of("opt"), Some("default"));
     assert!(m.is_present("flag"));
 }
+
+#[test]
+fn conflicts_with_all_either_order() {
+    let app = || {
+        App::new("conflict")
+            .arg(Arg::from_usage("-f, --flag 'some flag'")
+                .conflicts_with_all(&["opt", "other"]))
+            .arg(Arg::from_usage("-o, --opt=[opt] 'some opt'"))
+            .arg(Arg::from_usage("--other 'other flag'"))
+    };
+
+    for args in &[
+        vec!["myprog", "-f", "--other"],
+        vec!["myprog", "--other", "-f"],
+        vec!["myprog", "-o", "val", "-f"],
+        vec!["myprog", "-f", "-o", "val"],
+    ] {
+        let result = app().get_matches_from_safe(args.clone());
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().kind, ErrorKind::ArgumentConflict);
+    }
+
+    let result = app().get_matches_from_safe(vec!["myprog", "-o", "val", "--other"]);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn conflicting_opts_with_values() {
+    let app = || {
+        App::new("conflict")
+            .arg(Arg::from_usage("--world [name] 'the world to join'").conflicts_with("server"))
+            .arg(Arg::from_usage("--server [addr] 'the server to join'"))
+    };
+
+    for args in &[
+        vec!["myprog", "--world", "main", "--server", "localhost"],
+        vec!["myprog", "--server=localhost", "--world=main"],
+    ] {
+        let err = app().get_matches_from_safe(args.clone()).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::ArgumentConflict);
+        assert!(err.message.contains("--world <name>"), "{}", err.message);
+        assert!(err.message.contains("--server <addr>"), "{}", err.message);
+    }
+
+    let m = app().get_matches_from(vec!["myprog", "--server", "localhost"]);
+    assert_eq!(m.value_of("server"), Some("localhost"));
+}
+
+#[test]
+fn validate_with_custom_rules() {
+    let app = App::new("prog")
+        .setting(AppSettings::ColorNever)
+        .arg(Arg::with_name("mode").long("mode").takes_value(true))
+        .arg(Arg::with_name("speed").long("speed").takes_value(true))
+        .validate_with(|m| {
+            if m.value_of("mode") == Some("walk") && m.value_of("speed") == Some("fast") {
+                Err("walking can't be fast".to_owned())
+            } else {
+                Ok(())
+            }
+        })
+        .validate_with(|m| {
+            if m.is_present("speed") && !m.is_present("mode") {
+                Err("a speed needs a mode".to_owned())
+            } else {
+                Ok(())
+            }
+        });
+
+    assert!(app.clone().get_matches_from_safe(vec!["prog", "--mode", "walk"]).is_ok());
+    assert!(app.clone()
+        .get_matches_from_safe(vec!["prog", "--mode", "run", "--speed", "fast"])
+        .is_ok());
+
+    let err = app.clone()
+        .get_matches_from_safe(vec!["prog", "--mode", "walk", "--speed", "fast"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueValidation);
+    assert!(err.message.starts_with("error: walking can't be fast\n"), "{}", err.message);
+
+    let err = app.get_matches_from_safe(vec!["prog", "--speed", "fast"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueValidation);
+    assert!(err.message.starts_with("error: a speed needs a mode\n"), "{}", err.message);
+}
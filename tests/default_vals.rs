@@ -3,7 +3,9 @@ extern crate regex;
 
 include!("../clap-test.rs");
 
-use clap::{App, Arg, ErrorKind};
+use std::env;
+
+use clap::{App, AppSettings, Arg, ErrorKind};
 
 #[test]
 fn opts() {
@@ -496,3 +498,45 @@ fn issue_1050_num_vals_and_defaults() {
     let m = res.unwrap();
     assert_eq!(m.value_of("exit-code"), Some("1"));
 }
+
+#[test]
+fn default_val_env_expansion() {
+    env::set_var("CLP_DEFAULT_VAL_DIR", "/opt/app");
+    env::remove_var("CLP_DEFAULT_VAL_UNSET");
+    let app = App::new("df")
+        .setting(AppSettings::ExpandDefaultEnvVars)
+        .arg(Arg::from_usage("--config [config] 'some opt'")
+            .default_value("${CLP_DEFAULT_VAL_DIR}/$HOME/${CLP_DEFAULT_VAL_UNSET}x.toml"))
+        .arg(Arg::from_usage("--lit [lit] 'some opt'").default_value("${unclosed"));
+
+    let m = app.get_matches_from_safe(vec![""]).unwrap();
+    assert_eq!(m.value_of("config"), Some("/opt/app/$HOME/x.toml"));
+    assert_eq!(m.value_of("lit"), Some("${unclosed"));
+}
+
+#[test]
+fn default_val_env_expansion_off_by_default() {
+    env::set_var("CLP_DEFAULT_VAL_DIR", "/opt/app");
+    let m = App::new("df")
+        .arg(Arg::from_usage("--config [config] 'some opt'").default_value("${CLP_DEFAULT_VAL_DIR}"))
+        .get_matches_from_safe(vec![""])
+        .unwrap();
+    assert_eq!(m.value_of("config"), Some("${CLP_DEFAULT_VAL_DIR}"));
+}
+
+#[test]
+fn default_val_env_expansion_unset_error() {
+    env::remove_var("CLP_DEFAULT_VAL_UNSET");
+    let app = App::new("df")
+        .setting(AppSettings::RequireDefaultEnvVars)
+        .arg(Arg::from_usage("--config [config] 'some opt'")
+            .default_value("${CLP_DEFAULT_VAL_UNSET}/x.toml"));
+
+    let err = app.clone().get_matches_from_safe(vec![""]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueValidation);
+    assert!(err.message.contains("CLP_DEFAULT_VAL_UNSET"));
+
+    // The default isn't expanded when it isn't used
+    let m = app.get_matches_from_safe(vec!["", "--config", "a.toml"]).unwrap();
+    assert_eq!(m.value_of("config"), Some("a.toml"));
+}
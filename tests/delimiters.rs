@@ -137,3 +137,20 @@ fn opt_eq_mult_def_delim() {
     assert_eq!(m.occurrences_of("option"), 1);
     assert_eq!(m.values_of("option").unwrap().collect::<Vec<_>>(), &["val1", "val2", "val3"]);
 }
+
+#[test]
+fn custom_delim_short_long_and_positional() {
+    let m = App::new("custom_delim")
+        .arg(Arg::with_name("perms")
+            .short("p")
+            .long("perms")
+            .multiple(true)
+            .value_delimiter(":"))
+        .arg(Arg::with_name("ranks")
+            .multiple(true)
+            .value_delimiter("|"))
+        .get_matches_from(vec!["", "-pbuild:mine", "--perms=chat", "guest|op,admin"]);
+
+    assert_eq!(m.values_of("perms").unwrap().collect::<Vec<_>>(), ["build", "mine", "chat"]);
+    assert_eq!(m.values_of("ranks").unwrap().collect::<Vec<_>>(), ["guest", "op,admin"]);
+}
@@ -243,3 +243,19 @@ fn unified_help_and_derive_order_subcommand_propagate_with_explicit_display_orde
 
     assert!(test::compare_output(app, "test sub --help", UNIFIED_DERIVE_SC_PROP_EXPLICIT_ORDER, false));
 }
+
+#[test]
+fn derive_order_subcommands() {
+    let app = App::new("test")
+        .setting(AppSettings::DeriveDisplayOrder)
+        .subcommand(SubCommand::with_name("zeta").about("first"))
+        .subcommand(SubCommand::with_name("beta").about("second"))
+        .subcommand(SubCommand::with_name("alpha").about("third"));
+
+    let help = app.gen_help();
+    assert_eq!(help, app.gen_help());
+    let zeta = help.find("zeta").unwrap();
+    let beta = help.find("beta").unwrap();
+    let alpha = help.find("alpha").unwrap();
+    assert!(zeta < beta && beta < alpha, "{}", help);
+}
@@ -3,7 +3,7 @@ extern crate clap;
 use std::env;
 use std::ffi::OsStr;
 
-use clap::{App, Arg};
+use clap::{App, Arg, ErrorKind};
 
 #[test]
 fn env() {
@@ -261,3 +261,52 @@ fn validator_invalid() {
 
     assert!(r.is_err());
 }
+
+#[test]
+fn required_satisfied_by_env() {
+    env::set_var("CLP_TEST_ENV_REQ", "env");
+
+    let r = App::new("df")
+        .arg(
+            Arg::from_usage("--arg <FILE> 'some arg'")
+                .required(true)
+                .env("CLP_TEST_ENV_REQ"),
+        )
+        .get_matches_from_safe(vec![""]);
+
+    assert!(r.is_ok());
+    assert_eq!(r.unwrap().value_of("arg").unwrap(), "env");
+}
+
+#[test]
+fn required_no_env() {
+    env::remove_var("CLP_TEST_ENV_REQ_NONE");
+
+    let r = App::new("df")
+        .arg(
+            Arg::from_usage("--arg <FILE> 'some arg'")
+                .required(true)
+                .env("CLP_TEST_ENV_REQ_NONE"),
+        )
+        .get_matches_from_safe(vec![""]);
+
+    assert!(r.is_err());
+    assert_eq!(r.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+}
+
+#[test]
+fn multiple_user_override() {
+    env::set_var("CLP_TEST_ENV_MULTI_OVERRIDE", "env1,env2");
+
+    let r = App::new("df")
+        .arg(
+            Arg::from_usage("--arg [FILE]... 'some arg'")
+                .env("CLP_TEST_ENV_MULTI_OVERRIDE")
+                .use_delimiter(true),
+        )
+        .get_matches_from_safe(vec!["", "--arg", "opt"]);
+
+    assert!(r.is_ok());
+    let m = r.unwrap();
+    assert_eq!(m.values_of("arg").unwrap().collect::<Vec<_>>(), vec!["opt"]);
+}
@@ -1,6 +1,6 @@
 extern crate clap;
 
-use clap::{App, Arg, ArgSettings};
+use clap::{App, AppSettings, Arg, ArgSettings, ErrorKind};
 
 #[test]
 fn flag_using_short() {
@@ -145,3 +145,109 @@ fn short_flag_name_missing() {
     assert!(a.v.num_vals.is_none());
 
 }
+
+#[test]
+fn flag_using_any_of_shorts() {
+    let app = || {
+        App::new("flag")
+            .arg(Arg::with_name("info").long("info").shorts(&['i', '?']))
+            .arg(Arg::from_usage("-v, --verbose 'some other flag'"))
+    };
+
+    for args in &[vec!["", "-i"], vec!["", "-?"], vec!["", "-v?"], vec!["", "--info"]] {
+        let m = app().get_matches_from(args.clone());
+        assert!(m.is_present("info"));
+    }
+
+    let m = App::new("flag")
+        .arg(Arg::with_name("info").shorts(&['i', '?']).multiple(true))
+        .get_matches_from(vec!["", "-?", "-i", "-?i"]);
+    assert_eq!(m.occurrences_of("info"), 4);
+}
+
+#[test]
+fn shorts_help_shows_primary() {
+    let mut help = Vec::new();
+    App::new("flag")
+        .arg(Arg::with_name("info").long("info").help("info").shorts(&['i', '?']))
+        .write_help(&mut help)
+        .unwrap();
+    let help = String::from_utf8(help).unwrap();
+    assert!(help.contains("-i, --info"));
+    assert!(!help.contains("-?"));
+}
+
+#[test]
+fn shorts_replace_help_short() {
+    let m = App::new("flag")
+        .arg(Arg::with_name("halp").shorts(&['?', 'h']))
+        .get_matches_from(vec!["", "-h"]);
+    assert!(m.is_present("halp"));
+}
+
+#[test]
+fn negatable_flag() {
+    let app = || {
+        App::new("flag")
+            .arg(Arg::with_name("color").long("color").short("c").negatable(true))
+            .arg(Arg::from_usage("--other 'not negatable'"))
+    };
+
+    let m = app().get_matches_from(vec!["", "--color"]);
+    assert!(m.is_present("color"));
+    assert_eq!(m.flag_state("color"), Some(true));
+
+    let m = app().get_matches_from(vec!["", "--no-color"]);
+    assert!(!m.is_present("color"));
+    assert_eq!(m.flag_state("color"), Some(false));
+
+    let m = app().get_matches_from(vec!["", "-c", "--no-color"]);
+    assert!(!m.is_present("color"));
+    assert_eq!(m.flag_state("color"), Some(false));
+
+    let m = app().get_matches_from(vec!["", "--no-color", "-c"]);
+    assert!(m.is_present("color"));
+    assert_eq!(m.flag_state("color"), Some(true));
+
+    let m = app().get_matches_from(vec!["", "--other"]);
+    assert_eq!(m.flag_state("color"), None);
+    assert_eq!(m.flag_state("other"), Some(true));
+
+    let res = app().get_matches_from_safe(vec!["", "--no-other"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
+
+#[test]
+fn negatable_flag_with_value() {
+    let app = || {
+        App::new("flag")
+            .arg(Arg::with_name("color").long("color").negatable(true))
+    };
+
+    let m = app().get_matches_from(vec!["", "--color=false"]);
+    assert!(!m.is_present("color"));
+    assert_eq!(m.flag_state("color"), Some(false));
+
+    let m = app().get_matches_from(vec!["", "--color=true"]);
+    assert!(m.is_present("color"));
+    assert_eq!(m.flag_state("color"), Some(true));
+
+    let res = app().get_matches_from_safe(vec!["", "--color=maybe"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::InvalidValue);
+}
+
+#[test]
+fn did_you_mean_long_flag() {
+    let app = App::new("prog")
+        .setting(AppSettings::ColorNever)
+        .arg(Arg::with_name("verbose").long("verbose"))
+        .arg(Arg::with_name("version-check").long("version-check"));
+
+    let err = app.clone().get_matches_from_safe(vec!["prog", "--verbse"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownArgument);
+    assert!(err.message.contains("\n\tDid you mean --verbose?\n"), "{}", err.message);
+
+    let err = app.get_matches_from_safe(vec!["prog", "--quiet"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownArgument);
+    assert!(!err.message.contains("Did you mean"), "{}", err.message);
+}
@@ -4,7 +4,7 @@ extern crate regex;
 #[cfg(test)]
 mod tests {
     include!("../clap-test.rs");
-    use clap::{App, Arg, SubCommand};
+    use clap::{App, Arg, ErrorKind, SubCommand};
 
     fn get_app() -> App<'static, 'static> {
         App::new("myprog")
@@ -34,4 +34,84 @@ mod tests {
         let _ = app.get_matches_from_safe_borrow(vec!["myprog"]);
         let _ = app.get_matches_from_safe_borrow(vec!["myprog"]);
     }
+
+    #[test]
+    fn global_short_flag_after_grandchild() {
+        let m = App::new("myprog")
+            .arg(Arg::with_name("verbose")
+                .short("v")
+                .global(true))
+            .subcommand(SubCommand::with_name("outer")
+                .subcommand(SubCommand::with_name("inner")
+                    .arg(Arg::with_name("input"))))
+            .get_matches_from(vec!["myprog", "outer", "inner", "file", "-v"]);
+
+        assert!(m.is_present("verbose"));
+        let outer = m.subcommand_matches("outer").unwrap();
+        assert!(outer.is_present("verbose"));
+        let inner = outer.subcommand_matches("inner").unwrap();
+        assert!(inner.is_present("verbose"));
+        assert_eq!(inner.value_of("input"), Some("file"));
+    }
+
+    #[test]
+    fn global_arg_relationships_enforced_in_subcommand() {
+        let app = || {
+            App::new("myprog")
+                .arg(Arg::with_name("quiet")
+                    .long("quiet")
+                    .global(true)
+                    .conflicts_with("verbose"))
+                .arg(Arg::with_name("world")
+                    .long("world")
+                    .takes_value(true)
+                    .global(true)
+                    .requires("save"))
+                .subcommand(SubCommand::with_name("sub")
+                    .arg(Arg::with_name("verbose").long("verbose"))
+                    .arg(Arg::with_name("save").long("save")))
+        };
+
+        let err = app()
+            .get_matches_from_safe(vec!["myprog", "sub", "--quiet", "--verbose"])
+            .unwrap_err();
+        assert_eq!(err.kind, ErrorKind::ArgumentConflict);
+        let err = app()
+            .get_matches_from_safe(vec!["myprog", "sub", "--verbose", "--quiet"])
+            .unwrap_err();
+        assert_eq!(err.kind, ErrorKind::ArgumentConflict);
+        let err = app()
+            .get_matches_from_safe(vec!["myprog", "--quiet", "sub", "--verbose"])
+            .unwrap_err();
+        assert_eq!(err.kind, ErrorKind::ArgumentConflict);
+
+        let err = app()
+            .get_matches_from_safe(vec!["myprog", "sub", "--world", "main"])
+            .unwrap_err();
+        assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+        let err = app()
+            .get_matches_from_safe(vec!["myprog", "--world", "main", "sub"])
+            .unwrap_err();
+        assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+
+        let m = app().get_matches_from(vec!["myprog", "--world", "main", "sub", "--save"]);
+        assert!(m.subcommand_matches("sub").unwrap().is_present("save"));
+        let m = app().get_matches_from(vec!["myprog", "sub", "--world", "main", "--save"]);
+        let sub_m = m.subcommand_matches("sub").unwrap();
+        assert_eq!(sub_m.value_of("world"), Some("main"));
+        assert!(sub_m.is_present("save"));
+        assert!(app().get_matches_from_safe(vec!["myprog", "sub", "--quiet"]).is_ok());
+    }
+
+    #[test]
+    fn global_arg_conflict_in_grandchild() {
+        let res = App::new("myprog")
+            .arg(Arg::with_name("quiet").long("quiet").global(true).conflicts_with("verbose"))
+            .subcommand(SubCommand::with_name("outer")
+                .subcommand(SubCommand::with_name("inner")
+                    .arg(Arg::with_name("verbose").long("verbose"))))
+            .get_matches_from_safe(vec!["myprog", "--quiet", "outer", "inner", "--verbose"]);
+
+        assert_eq!(res.unwrap_err().kind, ErrorKind::ArgumentConflict);
+    }
 }
@@ -205,3 +205,49 @@ fn group_multiple_args_error() {
     let err = result.unwrap_err();
     assert_eq!(err.kind, ErrorKind::ArgumentConflict);
 }
+
+#[test]
+fn group_member() {
+    let app = || {
+        App::new("group")
+            .args_from_usage("--fast 'go fast'
+                              --slow 'go slow'
+                              -v, --verbose 'be loud'")
+            .group(ArgGroup::with_name("mode")
+                .args(&["fast", "slow"])
+                .required(true))
+            .group(ArgGroup::with_name("any")
+                .args(&["slow", "verbose"])
+                .multiple(true))
+    };
+
+    let m = app().get_matches_from(vec!["group", "--slow"]);
+    assert_eq!(m.group_member("mode"), Some("slow"));
+    assert_eq!(m.group_member("any"), Some("slow"));
+
+    let m = app().get_matches_from(vec!["group", "-v", "--fast"]);
+    assert_eq!(m.group_member("mode"), Some("fast"));
+    assert_eq!(m.group_member("any"), Some("verbose"));
+    assert_eq!(m.group_member("nope"), None);
+
+    let m = app().get_matches_from(vec!["group", "--slow", "-v"]);
+    assert_eq!(m.group_member("any"), Some("slow"));
+}
+
+#[test]
+fn group_member_missing_and_conflict() {
+    let app = || {
+        App::new("group")
+            .args_from_usage("--fast 'go fast'
+                              --slow 'go slow'")
+            .group(ArgGroup::with_name("mode")
+                .args(&["fast", "slow"])
+                .required(true))
+    };
+
+    let err = app().get_matches_from_safe(vec!["group"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+
+    let err = app().get_matches_from_safe(vec!["group", "--fast", "--slow"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ArgumentConflict);
+}
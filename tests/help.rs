@@ -1203,3 +1203,332 @@ fn show_short_about_issue_897() {
             .long_about("Long about foo"));
     assert!(test::compare_output(app, "ctest foo -h", ISSUE_897_SHORT, false));
 }
+
+#[test]
+fn number_of_values_placeholders() {
+    let app = App::new("prog")
+        .setting(AppSettings::ColorNever)
+        .arg(Arg::with_name("coords")
+            .long("coords")
+            .number_of_values(2)
+            .help("x and z to teleport to"));
+
+    let help = app.clone().gen_help();
+    assert!(help.contains("--coords <coords> <coords>    x and z to teleport to"), "{}", help);
+
+    let m = app.clone().get_matches_from(vec!["prog", "--coords", "1", "2"]);
+    assert_eq!(m.values_of("coords").unwrap().collect::<Vec<_>>(), ["1", "2"]);
+
+    let err = app.get_matches_from_safe(vec!["prog", "--coords", "1"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::WrongNumberOfValues);
+}
+
+#[test]
+fn overflowing_arg_help_on_next_line() {
+    let app = App::new("prog")
+        .setting(AppSettings::ColorNever)
+        .arg(Arg::with_name("name")
+            .short("n")
+            .long("name")
+            .value_name("NAME")
+            .help("player to look up"))
+        .arg(Arg::with_name("target")
+            .short("t")
+            .long("teleport-destination-world")
+            .value_name("WORLD_NAME")
+            .help("world to send the player to"))
+        .arg(Arg::with_name("world")
+            .short("w")
+            .long("world")
+            .value_name("WORLD")
+            .help("world to search"));
+
+    let help = app.gen_help();
+    assert!(help.contains("
+OPTIONS:
+    -n, --name <NAME>      player to look up
+    -t, --teleport-destination-world <WORLD_NAME>
+            world to send the player to
+
+    -w, --world <WORLD>    world to search"), "{}", help);
+}
+
+#[test]
+fn arg_wider_than_term_help_on_next_line() {
+    let app = App::new("prog")
+        .setting(AppSettings::ColorNever)
+        .set_term_width(60)
+        .arg(Arg::with_name("name")
+            .short("n")
+            .long("name")
+            .value_name("NAME")
+            .help("player to look up"))
+        .arg(Arg::with_name("target")
+            .short("t")
+            .long("teleport-destination-world-of-the-player-named")
+            .value_name("WORLD_NAME")
+            .help("world to send to"));
+
+    let help = app.gen_help();
+    assert!(help.contains("
+OPTIONS:
+    -n, --name <NAME>    player to look up
+    -t, --teleport-destination-world-of-the-player-named <WORLD_NAME>
+            world to send to"), "{}", help);
+}
+
+#[test]
+fn custom_help_indent() {
+    let app = App::new("prog")
+        .setting(AppSettings::ColorNever)
+        .help_indent(2)
+        .arg(Arg::with_name("name")
+            .short("n")
+            .long("name")
+            .value_name("NAME")
+            .help("player to look up"))
+        .arg(Arg::with_name("world")
+            .long("world")
+            .value_name("WORLD")
+            .help("world to search"))
+        .arg(Arg::with_name("rank")
+            .help("rank to give"))
+        .subcommand(SubCommand::with_name("kick")
+            .arg(Arg::with_name("reason")
+                .long("reason")
+                .help("shown to the player")));
+
+    let help = app.clone().gen_help();
+    assert!(help.contains("
+USAGE:
+  prog [OPTIONS] [rank] [SUBCOMMAND]
+"), "{}", help);
+    assert!(help.contains("
+OPTIONS:
+  -n, --name <NAME>      player to look up
+      --world <WORLD>    world to search
+"), "{}", help);
+    assert!(help.contains("
+ARGS:
+  <rank>    rank to give
+"), "{}", help);
+
+    let err = app.get_matches_from_safe(vec!["prog", "help", "kick"]).unwrap_err();
+    assert!(err.message.contains("
+      --reason     shown to the player"), "{}", err.message);
+}
+
+#[test]
+fn help_subcommand_prints_named_subcommand_help() {
+    let app = App::new("prog")
+        .about("the parent")
+        .setting(AppSettings::ColorNever)
+        .subcommand(SubCommand::with_name("build")
+            .about("builds things")
+            .arg(Arg::with_name("release").long("release").help("in release mode"))
+            .subcommand(SubCommand::with_name("docs").about("builds the docs")))
+        .subcommand(SubCommand::with_name("test").about("tests things"));
+
+    let err = app.clone().get_matches_from_safe(vec!["prog", "help", "build"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.contains("builds things"), "{}", err.message);
+    assert!(err.message.contains("in release mode"), "{}", err.message);
+    assert!(!err.message.contains("the parent"), "{}", err.message);
+
+    let err = app.clone().get_matches_from_safe(vec!["prog", "help", "build", "docs"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.contains("builds the docs"), "{}", err.message);
+
+    let err = app.clone().get_matches_from_safe(vec!["prog", "help"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.contains("the parent"), "{}", err.message);
+    assert!(err.message.contains("tests things"), "{}", err.message);
+
+    let err = app.clone().get_matches_from_safe(vec!["prog", "help", "deploy"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnrecognizedSubcommand);
+    assert!(err.message.contains("'deploy'"), "{}", err.message);
+}
+
+#[test]
+fn subcommand_long_help_flag_prints_subcommand_help() {
+    let app = App::new("prog")
+        .about("the parent")
+        .setting(AppSettings::ColorNever)
+        .subcommand(SubCommand::with_name("build")
+            .about("builds things")
+            .arg(Arg::with_name("release").long("release").help("in release mode")));
+
+    for flag in &["--help", "-h"] {
+        let err = app.clone().get_matches_from_safe(vec!["prog", "build", flag]).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+        assert!(err.message.starts_with("prog build \nbuilds things\n"), "{}", err.message);
+        assert!(err.message.contains("USAGE:\n    prog build [FLAGS]\n"), "{}", err.message);
+        assert!(err.message.contains("in release mode"), "{}", err.message);
+        assert!(!err.message.contains("the parent"), "{}", err.message);
+    }
+}
+
+#[test]
+fn help_columns_aligned_per_section() {
+    let help = App::new("prog")
+        .setting(AppSettings::ColorNever)
+        .arg(Arg::with_name("c").short("c").help("short only"))
+        .arg(Arg::with_name("really-long-flag").long("really-long-flag").help("long only"))
+        .arg(Arg::with_name("out").short("o").long("out").value_name("FILE").help("output file"))
+        .arg(Arg::with_name("j").short("j").takes_value(true).help("jobs"))
+        .arg(Arg::with_name("input").help("input file"))
+        .arg(Arg::with_name("extra-positional").help("more input"))
+        .gen_help();
+
+    assert_eq!(help, "prog 
+
+USAGE:
+    prog [FLAGS] [OPTIONS] [ARGS]
+
+FLAGS:
+    -c                        short only
+    -h, --help                Prints help information
+        --really-long-flag    long only
+    -V, --version             Prints version information
+
+OPTIONS:
+    -j <j>              jobs
+    -o, --out <FILE>    output file
+
+ARGS:
+    <input>               input file
+    <extra-positional>    more input");
+}
+
+#[test]
+fn long_help_only_for_long_flag() {
+    let app = App::new("prog")
+        .setting(AppSettings::ColorNever)
+        .arg(Arg::with_name("world")
+            .long("world")
+            .takes_value(true)
+            .help("the world to join")
+            .long_help("The world to join once connected.\nDefaults to the main world."))
+        .arg(Arg::with_name("quiet").short("q").help("say less"));
+
+    let err = app.clone().get_matches_from_safe(vec!["prog", "-h"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.contains("the world to join"), "{}", err.message);
+    assert!(!err.message.contains("Defaults to the main world."), "{}", err.message);
+    assert!(err.message.contains("say less"), "{}", err.message);
+
+    let err = app.clone().get_matches_from_safe(vec!["prog", "--help"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.contains("The world to join once connected."), "{}", err.message);
+    assert!(err.message.contains("Defaults to the main world."), "{}", err.message);
+    assert!(!err.message.contains("the world to join"), "{}", err.message);
+    assert!(err.message.contains("say less"), "{}", err.message);
+}
+
+#[test]
+fn custom_help_headings() {
+    let help = App::new("prog")
+        .setting(AppSettings::ColorNever)
+        .arg(Arg::with_name("verbose").short("v").help("talk more"))
+        .arg(Arg::with_name("world")
+            .long("world")
+            .takes_value(true)
+            .help_heading("WORLD")
+            .help("the world to join"))
+        .arg(Arg::with_name("port").long("port").takes_value(true).help_heading("NETWORK").help("port"))
+        .arg(Arg::with_name("build").long("build").help_heading("WORLD").help("allow building"))
+        .arg(Arg::with_name("offline").long("offline").help_heading("NETWORK").help("stay offline"))
+        .arg(Arg::with_name("map").help_heading("WORLD").help("the map file"))
+        .arg(Arg::with_name("secret").long("secret").hidden(true).help_heading("HIDDEN"))
+        .gen_help();
+
+    assert_eq!(help, "prog 
+
+USAGE:
+    prog [FLAGS] [OPTIONS] [map]
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+    -v               talk more
+
+WORLD:
+        --world <world>    the world to join
+        --build            allow building
+    <map>                  the map file
+
+NETWORK:
+        --port <port>    port
+        --offline        stay offline");
+}
+
+#[test]
+fn display_order_reorders_sections() {
+    let app = || {
+        App::new("prog")
+            .setting(AppSettings::ColorNever)
+            .arg(Arg::with_name("zeta").long("zeta").help("z"))
+            .arg(Arg::with_name("alpha").long("alpha").help("a"))
+            .arg(Arg::with_name("mid").long("mid").display_order(1).help("m"))
+            .arg(Arg::with_name("out").long("out").takes_value(true).help("o"))
+            .arg(Arg::with_name("in").long("in").takes_value(true).display_order(0).help("i"))
+    };
+
+    let help = app().gen_help();
+    assert!(help.contains("FLAGS:
+        --mid        m
+        --alpha      a
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+        --zeta       z
+
+OPTIONS:
+        --in <in>      i
+        --out <out>    o"), "{}", help);
+
+    // with DeriveDisplayOrder, args without an explicit order are numbered by declaration
+    let help = app().setting(AppSettings::DeriveDisplayOrder).gen_help();
+    assert!(help.contains("FLAGS:
+        --zeta       z
+        --alpha      a
+        --mid        m
+    -h, --help       Prints help information
+    -V, --version    Prints version information"), "{}", help);
+}
+
+#[test]
+fn multiple_usage_lines() {
+    let app = || {
+        App::new("tar")
+            .setting(AppSettings::ColorNever)
+            .usage_line("tar -c [-f ARCHIVE] [FILE]...")
+            .usage_line("tar -x [-f ARCHIVE] [MEMBER]...")
+            .arg(Arg::with_name("create").short("c"))
+            .arg(Arg::with_name("extract").short("x"))
+    };
+
+    let mut out = vec![];
+    app().write_usage(&mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "USAGE:\n    tar -c [-f ARCHIVE] [FILE]...\n    tar -x [-f ARCHIVE] [MEMBER]...\n"
+    );
+
+    let help = app().gen_help();
+    assert!(
+        help.contains("USAGE:\n    tar -c [-f ARCHIVE] [FILE]...\n    tar -x [-f ARCHIVE] [MEMBER]...\n\n"),
+        "{}",
+        help
+    );
+
+    let err = app().get_matches_from_safe(vec!["tar", "--nope"]).unwrap_err();
+    assert!(
+        err.message.contains("USAGE:\n    tar -c [-f ARCHIVE] [FILE]...\n    tar -x [-f ARCHIVE] [MEMBER]...\n"),
+        "{}",
+        err.message
+    );
+
+    let mut out = vec![];
+    App::new("tar").write_usage(&mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "USAGE:\n    tar\n");
+}
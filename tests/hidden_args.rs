@@ -1,7 +1,7 @@
 extern crate clap;
 extern crate regex;
 
-use clap::{App, Arg};
+use clap::{App, AppSettings, Arg, SubCommand};
 
 include!("../clap-test.rs");
 
@@ -176,3 +176,31 @@ fn hidden_long_args_short_help() {
 
     assert!(test::compare_output(app, "test -h", HIDDEN_LONG_ARGS_SHORT_HELP, false));        
 }
+
+#[test]
+fn hidden_args_still_parse() {
+    let app = || {
+        App::new("test")
+            .setting(AppSettings::ColorNever)
+            .arg(Arg::with_name("legacy").long("legacy").hidden(true))
+            .arg(Arg::with_name("world").long("world").takes_value(true).hidden(true))
+            .arg(Arg::with_name("input").hidden(true))
+            .arg(Arg::with_name("verbose").long("verbose"))
+            .subcommand(SubCommand::with_name("debug").hidden(true))
+            .subcommand(SubCommand::with_name("build"))
+    };
+
+    let help = app().gen_help();
+    assert!(help.contains("--verbose"), "{}", help);
+    assert!(help.contains("build"), "{}", help);
+    assert!(!help.contains("legacy"), "{}", help);
+    assert!(!help.contains("--world"), "{}", help);
+    assert!(!help.contains("<input>"), "{}", help);
+    assert!(!help.contains("debug"), "{}", help);
+
+    let m = app().get_matches_from(vec!["test", "--legacy", "--world", "main", "file", "debug"]);
+    assert!(m.is_present("legacy"));
+    assert_eq!(m.value_of("world"), Some("main"));
+    assert_eq!(m.value_of("input"), Some("file"));
+    assert_eq!(m.subcommand_name(), Some("debug"));
+}
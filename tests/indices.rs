@@ -3,7 +3,7 @@ extern crate regex;
 
 include!("../clap-test.rs");
 
-use clap::{App, Arg};
+use clap::{App, AppSettings, Arg, ErrorKind, SubCommand, TokenKind};
 
 #[test]
 fn indices_mult_opts() {
@@ -173,3 +173,84 @@ fn indices_mult_opt_mult_flag() {
     assert_eq!(m.indices_of("option").unwrap().collect::<Vec<_>>(), &[2, 5]);
     assert_eq!(m.indices_of("flag").unwrap().collect::<Vec<_>>(), &[3, 6]);
 }
+
+#[test]
+fn spans_for_mixed_command() {
+    let app = || {
+        App::new("myapp")
+            .arg(Arg::with_name("verbose").short("v").long("verbose"))
+            .arg(Arg::with_name("world").short("w").long("world").takes_value(true))
+            .arg(Arg::with_name("input"))
+            .subcommand(SubCommand::with_name("tp")
+                .arg(Arg::with_name("speed").long("speed").takes_value(true))
+                .arg(Arg::with_name("player").multiple(true)))
+    };
+
+    let line = "myapp  -v --world main in.txt tp --speed=2 Notch -- -x";
+    let (res, spans) = app().get_matches_with_spans(line);
+    let m = res.unwrap();
+    assert_eq!(m.subcommand_matches("tp").unwrap().values_of("player").unwrap().collect::<Vec<_>>(), ["Notch", "-x"]);
+    let spans: Vec<_> = spans.into_iter().map(|(s, e, k)| (&line[s..e], k)).collect();
+    assert_eq!(spans, [
+        ("-v", TokenKind::Flag),
+        ("--world", TokenKind::Opt),
+        ("main", TokenKind::Value),
+        ("in.txt", TokenKind::Positional),
+        ("tp", TokenKind::Subcommand),
+        ("--speed=2", TokenKind::Opt),
+        ("Notch", TokenKind::Positional),
+        ("-x", TokenKind::Positional),
+    ]);
+
+    let (res, spans) = app().get_matches_with_spans("myapp -wmain --nope in.txt");
+    assert!(res.is_err());
+    assert_eq!(spans, [(6, 12, TokenKind::Opt)]);
+}
+
+#[test]
+fn spans_for_negative_numbers_and_hyphen_values() {
+    let app = || {
+        App::new("myapp")
+            .setting(AppSettings::AllowNegativeNumbers)
+            .arg(Arg::with_name("offset").long("offset").takes_value(true))
+            .arg(Arg::with_name("pattern").short("p").takes_value(true).allow_hyphen_values(true))
+            .arg(Arg::with_name("coords").multiple(true))
+    };
+
+    let line = "myapp -12 --offset -3 -p -x 4.5 -- -7 --offset";
+    let (res, spans) = app().get_matches_with_spans(line);
+    let m = res.unwrap();
+    assert_eq!(m.values_of("coords").unwrap().collect::<Vec<_>>(), ["-12", "4.5", "-7", "--offset"]);
+    let spans: Vec<_> = spans.into_iter().map(|(s, e, k)| (&line[s..e], k)).collect();
+    assert_eq!(spans, [
+        ("-12", TokenKind::Positional),
+        ("--offset", TokenKind::Opt),
+        ("-3", TokenKind::Value),
+        ("-p", TokenKind::Opt),
+        ("-x", TokenKind::Value),
+        ("4.5", TokenKind::Positional),
+        ("-7", TokenKind::Positional),
+        ("--offset", TokenKind::Positional),
+    ]);
+}
+
+#[test]
+fn spans_for_help_subcommand() {
+    let app = || {
+        App::new("myapp")
+            .arg(Arg::with_name("verbose").short("v"))
+            .subcommand(SubCommand::with_name("tp")
+                .subcommand(SubCommand::with_name("home")))
+    };
+
+    let line = "myapp -v help tp home";
+    let (res, spans) = app().get_matches_with_spans(line);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::HelpDisplayed);
+    let spans: Vec<_> = spans.into_iter().map(|(s, e, k)| (&line[s..e], k)).collect();
+    assert_eq!(spans, [
+        ("-v", TokenKind::Flag),
+        ("help", TokenKind::Subcommand),
+        ("tp", TokenKind::Subcommand),
+        ("home", TokenKind::Subcommand),
+    ]);
+}
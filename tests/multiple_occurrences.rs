@@ -73,3 +73,39 @@ fn multiple_occurrences_of_flags_large_quantity() {
     assert!(m.is_present("multflag"));
     assert_eq!(m.occurrences_of("multflag"), 1024);
 }
+
+#[test]
+fn multiple_occurrences_of_flags_combined_short() {
+    let app = App::new("mo_flags_combined")
+                .arg(Arg::from_usage("-v, --verbose 'verbosity level'")
+                    .multiple(true))
+                .arg(Arg::from_usage("-q, --quiet 'less output'"));
+    let m = app.clone().get_matches_from(vec!["", "-vvv"]);
+    assert_eq!(m.occurrences_of("verbose"), 3);
+    assert_eq!(m.occurrences_of("quiet"), 0);
+
+    let m = app.get_matches_from(vec!["", "-vqv", "--verbose"]);
+    assert_eq!(m.occurrences_of("verbose"), 3);
+    assert_eq!(m.occurrences_of("quiet"), 1);
+}
+
+#[test]
+fn count_flag_many_repetitions() {
+    let cluster = "v".repeat(300);
+    let mut args = vec!["prog".to_owned(), format!("-{}", cluster), "--verbose".to_owned()];
+    args.extend(vec!["-v".to_owned(); 700]);
+    let m = App::new("prog")
+        .arg(Arg::with_name("verbose").short("v").long("verbose").count(true))
+        .arg(Arg::with_name("quiet").short("q").count(true))
+        .get_matches_from(args);
+
+    assert_eq!(m.count_of("verbose"), 1001);
+    assert_eq!(m.count_of("quiet"), 0);
+    assert!(!m.is_present("quiet"));
+}
+
+#[test]
+#[should_panic(expected = "Argument \"level\" is a counter")]
+fn count_flag_cannot_take_value() {
+    App::new("prog").arg(Arg::with_name("level").long("level").takes_value(true).count(true));
+}
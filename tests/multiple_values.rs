@@ -1,6 +1,6 @@
 extern crate clap;
 
-use clap::{App, Arg, ErrorKind, SubCommand};
+use clap::{App, AppSettings, Arg, ErrorKind, SubCommand};
 
 #[test]
 fn option_long() {
@@ -1120,3 +1120,61 @@ fn multiple_vals_with_hyphen() {
     assert_eq!(&cmds, &["find", "-type", "f", "-name", "special"]);
     assert_eq!(m.value_of("location"), Some("/home/clap"));
 }
+
+#[test]
+fn option_exact_less_same_error_across_syntaxes() {
+    let app = App::new("multiple_values")
+        .setting(AppSettings::ColorNever)
+        .arg(Arg::with_name("option")
+            .short("o")
+            .long("option")
+            .help("multiple options")
+            .use_delimiter(true)
+            .number_of_values(3));
+
+    let errs: Vec<_> = vec![
+        vec!["", "--option", "val1", "val2"],
+        vec!["", "--option=val1,val2"],
+        vec!["", "--option", "val1,val2"],
+        vec!["", "-o", "val1", "val2"],
+        vec!["", "-o=val1,val2"],
+    ].into_iter()
+        .map(|argv| app.clone().get_matches_from_safe(argv).unwrap_err())
+        .collect();
+
+    for e in &errs {
+        assert_eq!(e.kind, ErrorKind::WrongNumberOfValues);
+        assert_eq!(e.message, errs[0].message);
+    }
+    assert!(errs[0].message.contains("requires 3 values, but 2 were provided"));
+}
+
+#[test]
+fn option_max_stops_consuming_values() {
+    let m = App::new("multiple_values")
+        .arg(Arg::with_name("option")
+            .short("o")
+            .takes_value(true)
+            .multiple(true)
+            .max_values(2))
+        .arg(Arg::with_name("file"))
+        .get_matches_from_safe(vec!["", "-o", "val1", "val2", "file.txt"]);
+
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().message);
+    let m = m.unwrap();
+    assert_eq!(m.values_of("option").unwrap().collect::<Vec<_>>(), ["val1", "val2"]);
+    assert_eq!(m.value_of("file"), Some("file.txt"));
+}
+
+#[test]
+fn option_min_requires_a_value() {
+    let m = App::new("multiple_values")
+        .arg(Arg::with_name("option")
+            .short("o")
+            .multiple(true)
+            .min_values(2))
+        .get_matches_from_safe(vec!["", "-o"]);
+
+    assert!(m.is_err());
+    assert_eq!(m.unwrap_err().kind, ErrorKind::EmptyValue);
+}
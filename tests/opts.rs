@@ -3,7 +3,7 @@ extern crate regex;
 
 include!("../clap-test.rs");
 
-use clap::{App, ArgMatches, Arg, ErrorKind};
+use clap::{App, AppSettings, ArgMatches, Arg, ErrorKind};
 
 #[cfg(feature = "suggestions")]
 static DYM: &'static str = "error: Found argument '--optio' which wasn't expected, or isn't valid in this context
@@ -459,3 +459,115 @@ fn issue_1105_empty_value_short_explicit_no_space() {
     let m = r.unwrap();
     assert_eq!(m.value_of("option"), Some(""));
 }
+
+#[test]
+fn value_name_in_help_and_usage() {
+    let mut app = App::new("prog")
+        .arg(Arg::with_name("config")
+            .long("config")
+            .value_name("FILE")
+            .required(true)
+            .help("config file"))
+        .arg(Arg::with_name("level")
+            .long("level")
+            .takes_value(true)
+            .help("log level"));
+
+    let mut help = vec![];
+    app.write_help(&mut help).unwrap();
+    let help = String::from_utf8(help).unwrap();
+    assert!(help.contains("--config <FILE>    config file"));
+    assert!(help.contains("--level <level>    log level"));
+
+    let err = app.get_matches_from_safe(vec!["prog"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+    assert!(err.message.contains("prog [OPTIONS] --config <FILE>"));
+}
+
+#[test]
+fn validator_reports_all_invalid_values() {
+    let err = App::new("prog")
+        .arg(Arg::with_name("num")
+            .long("num")
+            .multiple(true)
+            .takes_value(true)
+            .validator(|v| v.parse::<u8>().map(|_| ()).map_err(|_| format!("'{}' isn't a number", v))))
+        .get_matches_from_safe(vec!["prog", "--num", "1", "a", "2", "b"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueValidation);
+    assert!(err.message.contains("'a' isn't a number; 'b' isn't a number"));
+}
+
+#[test]
+fn opt_using_any_of_shorts() {
+    let app = || {
+        App::new("opts")
+            .arg(Arg::with_name("world").takes_value(true).shorts(&['w', 'W']))
+    };
+
+    assert_eq!(app().get_matches_from(vec!["", "-w", "main"]).value_of("world"), Some("main"));
+    assert_eq!(app().get_matches_from(vec!["", "-W", "main"]).value_of("world"), Some("main"));
+    assert_eq!(app().get_matches_from(vec!["", "-Wmain"]).value_of("world"), Some("main"));
+}
+
+#[test]
+fn validator_checks_short_long_and_positional_values() {
+    fn port(v: String) -> Result<(), String> {
+        v.parse::<u16>()
+            .map(|_| ())
+            .map_err(|_| String::from("must be a valid port number"))
+    }
+    let app = App::new("prog")
+        .setting(AppSettings::ColorNever)
+        .arg(Arg::with_name("port").short("p").long("port").takes_value(true).validator(port))
+        .arg(Arg::with_name("fallback").validator(port));
+
+    let m = app.clone()
+        .get_matches_from_safe(vec!["prog", "-p", "25565", "25566"])
+        .unwrap();
+    assert_eq!(m.value_of("port"), Some("25565"));
+    assert_eq!(m.value_of("fallback"), Some("25566"));
+
+    for args in &[
+        vec!["prog", "-p", "70000"],
+        vec!["prog", "--port=70000"],
+        vec!["prog", "70000"],
+    ] {
+        let err = app.clone().get_matches_from_safe(args.clone()).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::ValueValidation);
+        assert!(err.message.contains("must be a valid port number"), "{}", err.message);
+    }
+}
+
+#[test]
+fn from_usage_forms() {
+    let app = || {
+        App::new("prog")
+            .setting(AppSettings::ColorNever)
+            .arg(Arg::from_usage("-c, --config=[FILE] 'Sets a config file'"))
+            .arg(Arg::from_usage("--world <name> 'The world to join'"))
+            .arg(Arg::from_usage("<input> 'The input file'"))
+            .arg(Arg::from_usage("[output]... 'Output files'"))
+    };
+
+    let m = app().get_matches_from(vec!["prog", "--world", "main", "in.txt", "a", "b", "-c", "cfg.toml"]);
+    assert_eq!(m.value_of("config"), Some("cfg.toml"));
+    assert_eq!(m.value_of("world"), Some("main"));
+    assert_eq!(m.value_of("input"), Some("in.txt"));
+    assert_eq!(m.values_of("output").unwrap().collect::<Vec<_>>(), ["a", "b"]);
+
+    let m = app().get_matches_from(vec!["prog", "--config=x", "--world", "main", "in.txt"]);
+    assert_eq!(m.value_of("config"), Some("x"));
+    assert!(!m.is_present("output"));
+
+    for args in &[vec!["prog", "in.txt"], vec!["prog", "--world", "main"]] {
+        let err = app().get_matches_from_safe(args.clone()).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::MissingRequiredArgument, "{:?}", args);
+    }
+
+    let help = app().gen_help();
+    assert!(help.contains("-c, --config <FILE>    Sets a config file"), "{}", help);
+    assert!(help.contains("--world <name>     The world to join"), "{}", help);
+    assert!(help.contains("<input>        The input file"), "{}", help);
+    assert!(help.contains("<output>...    Output files"), "{}", help);
+}
@@ -0,0 +1,202 @@
+extern crate clap;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use clap::{App, AppSettings, Arg, Color, ColorWhen, Colorizer, ColorizerOption, ErrorKind, OutputKind,
+           OutputSink, Palette, SubCommand};
+
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<(String, OutputKind)>>>);
+
+impl OutputSink for Recorder {
+    fn write_line(&self, text: &str, kind: OutputKind) {
+        self.0.borrow_mut().push((text.to_owned(), kind));
+    }
+}
+
+#[test]
+fn sink_receives_error_lines() {
+    let sink = Recorder::default();
+    let err = App::new("prog")
+        .arg(Arg::with_name("input").required(true))
+        .get_matches_from_safe(vec!["prog"])
+        .unwrap_err();
+    err.write_to_sink(&sink);
+
+    let lines = sink.0.borrow();
+    assert!(lines.iter().all(|&(_, k)| k == OutputKind::Error));
+    assert_eq!(lines.len(), err.message.lines().count());
+    assert!(lines[0].0.contains("The following required arguments were not provided"));
+}
+
+#[test]
+fn sink_receives_help_lines() {
+    let sink = Recorder::default();
+    let err = App::new("prog")
+        .version("1.0")
+        .about("does things")
+        .output_sink(sink.clone())
+        .get_matches_from_safe(vec!["prog", "--help"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    err.write_to_sink(&sink);
+
+    let lines = sink.0.borrow();
+    assert_eq!(lines[0], ("prog 1.0".to_owned(), OutputKind::Info));
+    assert_eq!(lines[1], ("does things".to_owned(), OutputKind::Info));
+    assert!(lines.iter().all(|&(_, k)| k == OutputKind::Info));
+}
+
+#[test]
+fn sink_receives_version() {
+    let sink = Recorder::default();
+    let err = App::new("prog")
+        .version("1.0")
+        .subcommand(SubCommand::with_name("sub").version("2.0"))
+        .output_sink(sink.clone())
+        .get_matches_from_safe(vec!["prog", "sub", "-V"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::VersionDisplayed);
+    assert_eq!(*sink.0.borrow(), vec![("prog-sub 2.0".to_owned(), OutputKind::Info)]);
+}
+
+#[test]
+fn subcommands_inherit_version_and_author() {
+    let sink = Recorder::default();
+    let app = App::new("prog")
+        .version("1.0")
+        .author("Kevin K.")
+        .setting(AppSettings::ColorNever)
+        .subcommand(SubCommand::with_name("inherit"))
+        .subcommand(SubCommand::with_name("own").version("2.0").author("Someone Else"))
+        .output_sink(sink.clone());
+
+    for &(sc, version) in &[("inherit", "prog-inherit 1.0"), ("own", "prog-own 2.0")] {
+        sink.0.borrow_mut().clear();
+        let err = app.clone().get_matches_from_safe(vec!["prog", sc, "-V"]).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::VersionDisplayed);
+        assert_eq!(*sink.0.borrow(), vec![(version.to_owned(), OutputKind::Info)]);
+    }
+
+    for &(sc, title, author) in &[("inherit", "prog inherit 1.0", "Kevin K."), ("own", "prog own 2.0", "Someone Else")] {
+        let err = app.clone().get_matches_from_safe(vec!["prog", sc, "--help"]).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+        let lines: Vec<_> = err.message.lines().collect();
+        assert_eq!(lines[0].trim_end(), title);
+        assert_eq!(lines[1], author);
+    }
+}
+
+#[cfg(feature = "color")]
+#[test]
+fn color_auto_for_chat_colors_only_chat_output() {
+    let sink = Recorder::default();
+    let app = App::new("prog")
+        .about("does things")
+        .arg(Arg::with_name("verbose").short("v").help("be loud"))
+        .subcommand(SubCommand::with_name("sub"))
+        .color_auto_for_chat(sink.clone());
+
+    let err = app.clone()
+        .get_matches_from_safe(vec!["prog", "sub", "--help"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    err.write_to_sink(&sink);
+    assert!(sink.0.borrow().iter().any(|&(ref l, _)| l == "USAGE:&f"));
+
+    let err = app.clone()
+        .get_matches_from_safe(vec!["prog", "--nope"])
+        .unwrap_err();
+    assert!(err.message.starts_with("&cerror:&f"), "{}", err.message);
+
+    let help = app.gen_help();
+    assert!(help.contains("\nUSAGE:\n"), "{}", help);
+    assert!(!help.contains('&'), "{}", help);
+}
+
+#[cfg(feature = "color")]
+#[test]
+fn palette_colors_help_and_errors() {
+    let app = App::new("prog")
+        .setting(AppSettings::ColorAlways)
+        .setting(AppSettings::ColoredHelp)
+        .palette(Palette {
+            good: Color::Magenta,
+            warning: Color::Blue,
+            error: Color::Yellow,
+            info: Color::Green,
+        })
+        .arg(Arg::with_name("verbose").short("v").help("be loud"))
+        .subcommand(SubCommand::with_name("sub"));
+
+    let err = app.clone().get_matches_from_safe(vec!["prog", "--help"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.contains("&9FLAGS:\n&f"), "{}", err.message);
+    assert!(err.message.contains("&d-v&f"), "{}", err.message);
+
+    for args in &[vec!["prog", "--nope"], vec!["prog", "sub", "--nope"]] {
+        let err = app.clone().get_matches_from_safe(args.clone()).unwrap_err();
+        assert!(err.message.starts_with("&eerror:&f"), "{}", err.message);
+        assert!(err.message.contains("&9--nope&f"), "{}", err.message);
+        assert!(err.message.contains("For more information try &a--help&f"), "{}", err.message);
+    }
+}
+
+#[cfg(feature = "color")]
+#[test]
+fn colorizer_with_palette() {
+    let c = Colorizer::with_palette(
+        ColorizerOption {
+            use_stderr: false,
+            when: ColorWhen::Always,
+        },
+        Palette {
+            error: Color::Magenta,
+            ..Palette::default()
+        },
+    );
+    assert_eq!(&*format!("{}", c.error("error:")), "&derror:&f");
+    assert_eq!(&*format!("{}", c.good("good")), "&agood&f");
+}
+
+#[test]
+fn errors_use_stderr_and_help_uses_stdout() {
+    let app = || {
+        App::new("prog")
+            .version("1.0")
+            .setting(AppSettings::ColorNever)
+            .arg(Arg::with_name("input").required(true))
+    };
+
+    for args in &[vec!["prog"], vec!["prog", "--nope"], vec!["prog", "a", "b"]] {
+        let err = app().get_matches_from_safe(args.clone()).unwrap_err();
+        assert!(err.use_stderr(), "{:?}: {:?}", args, err.kind);
+        assert!(err.message.contains("For more information try --help"), "{}", err.message);
+    }
+    for args in &[vec!["prog", "--help"], vec!["prog", "-V"]] {
+        let err = app().get_matches_from_safe(args.clone()).unwrap_err();
+        assert!(!err.use_stderr(), "{:?}: {:?}", args, err.kind);
+    }
+}
+
+#[cfg(not(feature = "color"))]
+#[test]
+fn no_color_codes_without_color_feature() {
+    fn has_color_code(s: &str) -> bool {
+        s.as_bytes().windows(2).any(|w| w[0] == b'&' && (w[1] as char).is_digit(16))
+    }
+
+    let app = || {
+        App::new("prog")
+            .setting(AppSettings::ColorAlways)
+            .arg(Arg::with_name("config").long("config").takes_value(true).help("Sets a config file"))
+    };
+
+    let err = app().get_matches_from_safe(vec!["prog", "--nope"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownArgument);
+    assert!(!has_color_code(&err.message), "{}", err.message);
+
+    let err = app().get_matches_from_safe(vec!["prog", "--help"]).unwrap_err();
+    assert!(!has_color_code(&err.message), "{}", err.message);
+}
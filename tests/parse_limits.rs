@@ -0,0 +1,126 @@
+extern crate clap;
+
+use std::{env, fs, process};
+
+use clap::{App, AppSettings, Arg, ErrorKind, ParseLimits, SubCommand};
+
+fn limited_app() -> App<'static, 'static> {
+    App::new("prog")
+        .setting(AppSettings::ColorNever)
+        .parse_limits(ParseLimits::new()
+            .max_tokens(8)
+            .max_token_len(16)
+            .max_values(3))
+        .arg(Arg::with_name("opt")
+            .short("o")
+            .takes_value(true)
+            .multiple(true)
+            .use_delimiter(true))
+        .arg(Arg::with_name("input").multiple(true))
+        .subcommand(SubCommand::with_name("sub")
+            .arg(Arg::with_name("files").multiple(true)))
+}
+
+#[test]
+fn within_limits() {
+    let m = limited_app().get_matches_from_safe(vec!["prog", "in", "-o", "a,b", "-o", "c"]);
+
+    assert!(m.is_ok());
+    let m = m.unwrap();
+    assert_eq!(m.values_of("opt").unwrap().collect::<Vec<_>>(), ["a", "b", "c"]);
+    assert_eq!(m.value_of("input"), Some("in"));
+}
+
+#[test]
+fn too_many_tokens() {
+    // Far more input than is allowed, none of which past the limit should ever be read
+    let args = (0..).map(|i| if i == 0 { "prog".to_owned() } else { i.to_string() });
+    let res = limited_app().get_matches_from_safe(args);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::LimitExceeded);
+    assert_eq!(err.info, Some(vec!["arguments".to_owned(), "8".to_owned()]));
+    assert_eq!(err.message, "error: The maximum number of arguments (8) was exceeded");
+}
+
+#[test]
+fn token_too_long() {
+    let long = "x".repeat(1 << 20);
+    let res = limited_app().get_matches_from_safe(vec!["prog", &*long]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::LimitExceeded);
+    assert_eq!(err.info, Some(vec!["bytes per argument".to_owned(), "16".to_owned()]));
+}
+
+#[test]
+fn too_many_values_delimited() {
+    let res = limited_app().get_matches_from_safe(vec!["prog", "-o", "a,b,c,d,e,f,g,h"]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::LimitExceeded);
+    assert_eq!(
+        err.info,
+        Some(vec!["values".to_owned(), "3".to_owned(), "opt".to_owned()])
+    );
+    assert_eq!(
+        err.message,
+        "error: The maximum number of values (3) was exceeded for '-o <opt>...'"
+    );
+}
+
+#[test]
+fn too_many_values_subcommand() {
+    let res = limited_app().get_matches_from_safe(vec!["prog", "sub", "a", "b", "c", "d"]);
+
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind, ErrorKind::LimitExceeded);
+}
+
+#[test]
+fn unlimited_by_default() {
+    let args = vec!["prog"]
+        .into_iter()
+        .map(|s| s.to_owned())
+        .chain((0..1000).map(|i| i.to_string()));
+    let m = App::new("prog")
+        .arg(Arg::with_name("input").multiple(true))
+        .get_matches_from_safe(args);
+
+    assert!(m.is_ok());
+    assert_eq!(m.unwrap().values_of("input").unwrap().count(), 1000);
+}
+
+#[test]
+fn limits_apply_while_expanding_response_files() {
+    let dir = env::temp_dir().join(format!("clap-parse-limits-{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let many = dir.join("many.txt");
+    let long = dir.join("long.txt");
+    fs::write(&many, vec!["a"; 10000].join(" ")).unwrap();
+    fs::write(&long, "x".repeat(1 << 16)).unwrap();
+
+    for file in &[&many, &long] {
+        let err = limited_app()
+            .setting(AppSettings::AllowResponseFiles)
+            .get_matches_from_safe(vec!["prog".to_owned(), format!("@{}", file.display())])
+            .unwrap_err();
+        assert_eq!(err.kind, ErrorKind::LimitExceeded, "{}", err.message);
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn limits_apply_while_replacing() {
+    let err = limited_app()
+        .replace("all", &["a"; 100])
+        .get_matches_from_safe(vec!["prog", "all"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::LimitExceeded);
+    assert_eq!(err.info, Some(vec!["arguments".to_owned(), "8".to_owned()]));
+}
@@ -1,6 +1,6 @@
 extern crate clap;
 
-use clap::{App, Arg, ErrorKind};
+use clap::{App, Arg, ErrorKind, SubCommand};
 
 #[test]
 fn only_pos_follow() {
@@ -272,3 +272,121 @@ fn last_positional_second_to_last_mult() {
         .get_matches_from_safe(vec!["test", "tgt", "crp1", "crp2", "--", "arg"]);
     assert!(r.is_ok(), "{:?}", r.unwrap_err().kind);
 }
+
+#[test]
+fn last_positional_takes_everything_after_double_dash() {
+    let m = App::new("tool")
+        .subcommand(SubCommand::with_name("run")
+            .arg(Arg::with_name("verbose").long("verbose"))
+            .arg(Arg::with_name("target"))
+            .arg(Arg::with_name("cmd").multiple(true).last(true)))
+        .get_matches_from(vec!["tool", "run", "--verbose", "--", "cmd", "--flags", "here", "--"]);
+
+    let run_m = m.subcommand_matches("run").unwrap();
+    assert!(run_m.is_present("verbose"));
+    assert!(!run_m.is_present("target"));
+    assert_eq!(
+        run_m.values_of("cmd").unwrap().collect::<Vec<_>>(),
+        ["cmd", "--flags", "here", "--"]
+    );
+}
+
+#[test]
+fn occurrences_of_positionals() {
+    let m = App::new("prog")
+        .arg(Arg::with_name("target"))
+        .arg(Arg::with_name("players").multiple(true))
+        .arg(Arg::with_name("reason").long("reason").takes_value(true))
+        .get_matches_from(vec!["prog", "spawn", "Notch", "jeb_", "Dinnerbone"]);
+
+    assert_eq!(m.occurrences_of("target"), 1);
+    assert_eq!(m.occurrences_of("players"), 3);
+    assert_eq!(m.occurrences_of("reason"), 0);
+}
+
+#[test]
+#[should_panic(expected = "Argument \"second\" has the same index (1) as another positional argument")]
+fn duplicate_positional_index() {
+    App::new("prog")
+        .arg(Arg::with_name("first").index(1))
+        .arg(Arg::with_name("second").index(1));
+}
+
+#[test]
+#[should_panic(expected = "Found positional argument \"third\" whose index is 3 but there is no positional argument with index 2")]
+fn gapped_positional_indices() {
+    App::new("prog")
+        .arg(Arg::with_name("first").index(1))
+        .arg(Arg::with_name("third").index(3))
+        .get_matches_from(vec!["prog", "a", "b"]);
+}
+
+#[test]
+#[should_panic(expected = "Found positional argument \"fourth\" whose index is 4 but there is no positional argument with index 1")]
+fn positional_indices_not_starting_at_one() {
+    App::new("prog")
+        .arg(Arg::with_name("second").index(2))
+        .arg(Arg::with_name("third").index(3))
+        .arg(Arg::with_name("fourth").index(4))
+        .get_matches_from(vec!["prog", "a", "b", "c"]);
+}
+
+#[test]
+fn explicit_positional_indices_with_options() {
+    let m = App::new("prog")
+        .arg(Arg::with_name("second").index(2))
+        .arg(Arg::with_name("opt").long("opt").takes_value(true))
+        .arg(Arg::with_name("first").index(1))
+        .arg(Arg::with_name("flag").short("f"))
+        .get_matches_from(vec!["prog", "a", "--opt", "x", "b", "-f"]);
+
+    assert_eq!(m.value_of("first"), Some("a"));
+    assert_eq!(m.value_of("second"), Some("b"));
+    assert_eq!(m.value_of("opt"), Some("x"));
+    assert!(m.is_present("flag"));
+}
+
+#[test]
+fn required_then_optional_positional() {
+    let app = || {
+        App::new("prog")
+            .arg(Arg::from_usage("<src> 'source'"))
+            .arg(Arg::from_usage("[dest] 'destination'"))
+            .arg(Arg::from_usage("-f, --force 'overwrite'"))
+    };
+
+    let m = app().get_matches_from(vec!["prog", "a.txt"]);
+    assert_eq!(m.value_of("src"), Some("a.txt"));
+    assert!(!m.is_present("dest"));
+
+    let m = app().get_matches_from(vec!["prog", "a.txt", "-f", "b.txt"]);
+    assert_eq!(m.value_of("src"), Some("a.txt"));
+    assert_eq!(m.value_of("dest"), Some("b.txt"));
+    assert!(m.is_present("force"));
+
+    let err = app().get_matches_from_safe(vec!["prog"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+}
+
+#[test]
+fn optional_positional_between_required_ones() {
+    use clap::AppSettings;
+
+    let app = || {
+        App::new("prog")
+            .setting(AppSettings::AllowMissingPositional)
+            .arg(Arg::from_usage("<player> 'player'"))
+            .arg(Arg::from_usage("[world] 'world'"))
+            .arg(Arg::from_usage("<rank> 'rank'"))
+    };
+
+    let m = app().get_matches_from(vec!["prog", "Notch", "op"]);
+    assert_eq!(m.value_of("player"), Some("Notch"));
+    assert!(!m.is_present("world"));
+    assert_eq!(m.value_of("rank"), Some("op"));
+
+    let m = app().get_matches_from(vec!["prog", "Notch", "main", "op"]);
+    assert_eq!(m.value_of("player"), Some("Notch"));
+    assert_eq!(m.value_of("world"), Some("main"));
+    assert_eq!(m.value_of("rank"), Some("op"));
+}
@@ -290,3 +290,30 @@ fn require_overriden_4() {
     let err = result.err().unwrap();
     assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
 }
+
+#[test]
+fn color_toggle_last_wins_in_short_clusters() {
+    let app = || {
+        App::new("posix")
+            .arg(Arg::from_usage("-c, --color 'use colors'").overrides_with("no-color"))
+            .arg(Arg::from_usage("-C, --no-color 'never use colors'").overrides_with("color"))
+            .arg(Arg::from_usage("-v, --verbose 'talk more'"))
+    };
+
+    let m = app().get_matches_from(vec!["", "-vcC"]);
+    assert!(m.is_present("no-color"));
+    assert!(!m.is_present("color"));
+    assert!(m.is_present("verbose"));
+
+    let m = app().get_matches_from(vec!["", "-Cvc"]);
+    assert!(m.is_present("color"));
+    assert!(!m.is_present("no-color"));
+
+    let m = app().get_matches_from(vec!["", "-c", "--no-color", "-vc"]);
+    assert!(m.is_present("color"));
+    assert!(!m.is_present("no-color"));
+
+    let m = app().get_matches_from(vec!["", "--color", "-vC"]);
+    assert!(m.is_present("no-color"));
+    assert!(!m.is_present("color"));
+}
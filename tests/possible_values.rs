@@ -208,6 +208,27 @@ fn case_insensitive() {
     );
 }
 
+#[test]
+fn case_insensitive_stores_declared_case() {
+    let m = App::new("pv")
+        .arg(
+            Arg::with_name("mode")
+                .long("mode")
+                .takes_value(true)
+                .possible_values(&["fast", "Slow"])
+                .case_insensitive(true),
+        )
+        .arg(
+            Arg::with_name("target")
+                .possible_value("World")
+                .case_insensitive(true),
+        )
+        .get_matches_from(vec!["pv", "--mode", "FAST", "world"]);
+
+    assert_eq!(m.value_of("mode"), Some("fast"));
+    assert_eq!(m.value_of("target"), Some("World"));
+}
+
 #[test]
 fn case_insensitive_faili() {
     let m = App::new("pv")
@@ -243,7 +264,7 @@ fn case_insensitive_multiple() {
     assert!(m.is_ok());
     assert_eq!(
         m.unwrap().values_of("option").unwrap().collect::<Vec<_>>(),
-        &["TeSt123", "teST123", "tESt321"]
+        &["test123", "test123", "test321"]
     );
 }
 
@@ -264,3 +285,48 @@ fn case_insensitive_multiple_fail() {
     assert!(m.is_err());
     assert_eq!(m.unwrap_err().kind, ErrorKind::InvalidValue);
 }
+
+#[test]
+fn possible_values_of_option_multiple_reports_all() {
+    let m = App::new("pv")
+        .arg(
+            Arg::with_name("mode")
+                .long("mode")
+                .takes_value(true)
+                .multiple(true)
+                .possible_value("fast")
+                .possible_value("slow"),
+        )
+        .get_matches_from_safe(vec!["", "--mode", "fast", "bogus1", "slow", "bogus2"]);
+
+    let err = m.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidValue);
+    assert_eq!(
+        err.info,
+        Some(vec!["mode".to_owned(), "bogus1".to_owned(), "bogus2".to_owned()])
+    );
+    assert!(err.message.contains("bogus1"));
+    assert!(err.message.contains("bogus2"));
+}
+
+#[test]
+fn possible_values_of_option_multiple_empty_value_checked_first() {
+    let app = || {
+        App::new("pv").arg(
+            Arg::with_name("mode")
+                .long("mode")
+                .takes_value(true)
+                .multiple(true)
+                .empty_values(false)
+                .possible_value("fast")
+                .possible_value("slow"),
+        )
+    };
+
+    for vals in &[["bogus1", ""], ["", "bogus1"]] {
+        let err = app()
+            .get_matches_from_safe(vec!["", "--mode", vals[0], vals[1]])
+            .unwrap_err();
+        assert_eq!(err.kind, ErrorKind::EmptyValue, "{:?}", vals);
+    }
+}
@@ -3,7 +3,7 @@ extern crate regex;
 
 include!("../clap-test.rs");
 
-use clap::{App, Arg, ErrorKind, ArgGroup};
+use clap::{App, AppSettings, Arg, ErrorKind, ArgGroup};
 
 static REQUIRE_EQUALS: &'static str = "error: The following required arguments were not provided:
     --opt=<FILE>
@@ -25,6 +25,8 @@ For more information try --help";
 static COND_REQ_IN_USAGE: &'static str = "error: The following required arguments were not provided:
     --output <output>
 
+required because '--target <target>' was used with the value 'file'
+
 USAGE:
     test --input <input> --output <output> --target <target>
 
@@ -284,6 +286,27 @@ fn required_unless_err() {
     assert_eq!(res.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
 }
 
+#[test]
+fn required_unless_present_anywhere() {
+    let app = || {
+        App::new("unlesstest")
+            .arg(Arg::with_name("world")
+                .required_unless("list"))
+            .arg(Arg::with_name("list").long("list"))
+            .arg(Arg::with_name("verbose").short("v").multiple(true))
+    };
+
+    let m = app().get_matches_from(vec!["unlesstest", "-v", "-v", "--list"]);
+    assert!(m.is_present("list"));
+    assert!(!m.is_present("world"));
+
+    let m = app().get_matches_from(vec!["unlesstest", "main", "-v"]);
+    assert_eq!(m.value_of("world"), Some("main"));
+
+    let err = app().get_matches_from_safe(vec!["unlesstest", "-v"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+}
+
 // REQUIRED_UNLESS_ALL
 
 #[test]
@@ -522,6 +545,53 @@ fn requires_if_present_val_no_present_pass() {
     assert!(res.is_ok());
 }
 
+fn requires_if_eq_any_app() -> App<'static, 'static> {
+    App::new("prog")
+        .setting(AppSettings::ColorNever)
+        .arg(Arg::with_name("format")
+            .requires_if_eq_any(&["json", "yaml"], "schema")
+            .takes_value(true)
+            .long("format"))
+        .arg(Arg::with_name("schema")
+            .takes_value(true)
+            .long("schema"))
+}
+
+#[test]
+fn requires_if_eq_any_match() {
+    let res = requires_if_eq_any_app()
+        .get_matches_from_safe(vec!["prog", "--format", "yaml"]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+    assert!(err.message.contains("--schema <schema>"));
+    assert!(err.message.contains("'--format <format>' was used with the value 'yaml'"));
+    assert_eq!(
+        err.info,
+        Some(vec!["schema".to_owned(), "format".to_owned(), "yaml".to_owned()])
+    );
+
+    let res = requires_if_eq_any_app()
+        .get_matches_from_safe(vec!["prog", "--format", "yaml", "--schema", "s.json"]);
+    assert!(res.is_ok());
+}
+
+#[test]
+fn requires_if_eq_any_no_match_pass() {
+    let res = requires_if_eq_any_app()
+        .get_matches_from_safe(vec!["prog", "--format", "toml"]);
+
+    assert!(res.is_ok());
+}
+
+#[test]
+fn requires_if_eq_any_absent_pass() {
+    let res = requires_if_eq_any_app().get_matches_from_safe(vec!["prog"]);
+
+    assert!(res.is_ok());
+}
+
 // Conditionally required
 
 #[test]
@@ -581,6 +651,30 @@ fn required_if_val_present_fail_error_output() {
                                  true));
 }
 
+#[test]
+fn required_if_error_names_both_args() {
+    let err = App::new("ri")
+        .setting(AppSettings::ColorNever)
+        .arg(Arg::with_name("mode")
+            .takes_value(true)
+            .long("mode"))
+        .arg(Arg::with_name("output")
+            .takes_value(true)
+            .required_if("mode", "file")
+            .long("output"))
+        .get_matches_from_safe(vec!["ri", "--mode", "file"])
+        .unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+    assert!(err.message.contains("--output <output>"), "{}", err.message);
+    assert!(
+        err.message.contains("required because '--mode <mode>' was used with the value 'file'"),
+        "{}",
+        err.message
+    );
+    assert_eq!(err.info, Some(vec!["output".to_owned(), "mode".to_owned(), "file".to_owned()]));
+}
+
 #[test]
 fn required_if_wrong_val() {
     let res = App::new("ri")
@@ -3,7 +3,7 @@ extern crate regex;
 
 include!("../clap-test.rs");
 
-use clap::{App, Arg, SubCommand, ErrorKind};
+use clap::{App, AppSettings, Arg, SubCommand, ErrorKind};
 
 static VISIBLE_ALIAS_HELP: &'static str = "clap-test 2.6
 
@@ -214,3 +214,109 @@ fn issue_1031_args_with_same_name_no_more_vals() {
     assert_eq!(m.value_of("ui-path"), Some("value"));
     assert_eq!(m.subcommand_name(), Some("signer"));
 }
+
+#[test]
+fn subcommand_handlers() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let app = || {
+        let (kick, ban) = (calls.clone(), calls.clone());
+        App::new("myprog")
+            .subcommand(SubCommand::with_name("kick")
+                .arg(Arg::with_name("player").required(true)))
+            .subcommand(SubCommand::with_name("ban")
+                .arg(Arg::with_name("player").required(true)))
+            .subcommand(SubCommand::with_name("list"))
+            .subcommand_handler("kick", move |m| {
+                kick.borrow_mut().push(format!("kick {}", m.value_of("player").unwrap()));
+            })
+            .subcommand_handler("ban", move |m| {
+                ban.borrow_mut().push(format!("ban {}", m.value_of("player").unwrap()));
+            })
+    };
+
+    let m = app().run_from(vec!["myprog", "ban", "Notch"]);
+    assert_eq!(m.subcommand_name(), Some("ban"));
+    assert_eq!(*calls.borrow(), ["ban Notch"]);
+
+    assert!(app().run_from_safe(vec!["myprog", "kick", "Steve"]).is_ok());
+    assert_eq!(*calls.borrow(), ["ban Notch", "kick Steve"]);
+
+    // No subcommand, a subcommand without a handler, or a failed parse run nothing
+    assert!(app().run_from_safe(vec!["myprog"]).is_ok());
+    assert!(app().run_from_safe(vec!["myprog", "list"]).is_ok());
+    assert!(app().run_from_safe(vec!["myprog", "kick"]).is_err());
+    assert_eq!(*calls.borrow(), ["ban Notch", "kick Steve"]);
+}
+
+#[test]
+fn get_matches_for_subcommand() {
+    let app = || {
+        App::new("server")
+            .global_setting(AppSettings::ColorNever)
+            .arg(Arg::with_name("verbose").short("v").global(true))
+            .arg(Arg::with_name("config").long("config").required(true).takes_value(true))
+            .subcommand(SubCommand::with_name("build")
+                .alias("b")
+                .arg(Arg::with_name("block").required(true))
+                .arg(Arg::with_name("count").long("count").takes_value(true)))
+    };
+
+    let m = app()
+        .get_matches_for_subcommand("build", vec!["stone", "--count", "64", "-v"])
+        .unwrap();
+    assert_eq!(m.value_of("block"), Some("stone"));
+    assert_eq!(m.value_of("count"), Some("64"));
+    assert!(m.is_present("verbose"));
+
+    let m = app().get_matches_for_subcommand("b", vec!["dirt"]).unwrap();
+    assert_eq!(m.value_of("block"), Some("dirt"));
+
+    let err = app().get_matches_for_subcommand("build", Vec::<&str>::new()).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+    assert!(err.message.contains("server build [FLAGS] [OPTIONS] <block>"), "{}", err.message);
+
+    let err = app().get_matches_for_subcommand("fly", vec!["up"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnrecognizedSubcommand);
+}
+
+#[test]
+#[cfg(feature = "suggestions")]
+fn subcmd_did_you_mean_close_typo_only() {
+    let app = App::new("pkg")
+        .setting(AppSettings::ColorNever)
+        .subcommand(SubCommand::with_name("install"))
+        .subcommand(SubCommand::with_name("remove"));
+
+    let err = app.clone().get_matches_from_safe(vec!["pkg", "instal"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidSubcommand);
+    assert!(err.message.contains("\n\tDid you mean 'install'?\n"), "{}", err.message);
+
+    let err = app.get_matches_from_safe(vec!["pkg", "zzz"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownArgument);
+    assert!(!err.message.contains("Did you mean"), "{}", err.message);
+}
+
+#[test]
+fn case_insensitive_subcommands() {
+    let app = || {
+        App::new("pkg")
+            .setting(AppSettings::CaseInsensitiveSubcommands)
+            .subcommand(SubCommand::with_name("build").alias("make")
+                .arg(Arg::with_name("release").long("release")))
+    };
+
+    let m = app().get_matches_from(vec!["pkg", "Build", "--release"]);
+    assert_eq!(m.subcommand_name(), Some("build"));
+    assert!(m.subcommand_matches("build").unwrap().is_present("release"));
+
+    let m = app().get_matches_from(vec!["pkg", "MAKE"]);
+    assert_eq!(m.subcommand_name(), Some("build"));
+
+    let res = App::new("pkg")
+        .subcommand(SubCommand::with_name("build"))
+        .get_matches_from_safe(vec!["pkg", "Build"]);
+    assert!(res.is_err());
+}
@@ -101,6 +101,25 @@ fn template_author_version() {
     assert!(test::compare_output(app, "MyApp --help", "Kevin K. <kbknapp@gmail.com>\n1.0\nDoes awesome things\nMyApp", false));
 }
 
+#[test]
+fn template_sections() {
+    let app = App::new("MyApp")
+                    .version("1.0")
+                    .args_from_usage("-c, --config=[FILE] 'Sets a custom config file'
+                                      <output>            'Sets an optional output file'
+                                      -d...               'Turn debugging information on'")
+                    .template("{bin}\n{flags}\n--\n{options}\n--\n{positionals}\n{nope}");
+    assert!(test::compare_output(app, "MyApp --help", "MyApp
+    -d               Turn debugging information on
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+--
+    -c, --config <FILE>    Sets a custom config file
+--
+    <output>    Sets an optional output file
+{nope}", false));
+}
+
 // ----------
 
 fn app_example1<'b, 'c>() -> App<'b, 'c> {
@@ -7,7 +7,7 @@ use std::str;
 
 include!("../clap-test.rs");
 
-use clap::{App, Arg};
+use clap::{App, AppSettings, Arg};
 
 static SCF2OP: &'static str = "flag present 2 times
 option NOT present
@@ -433,3 +433,86 @@ fn sc_short_flag_x2_short_opt_eq_pos() {
 fn sc_short_flag_x2_long_opt_eq_pos() {
     check_complex_output("clap-test subcmd value -f -f --option=some", SCF2OP);
 }
+
+#[test]
+fn write_usage_matches_help() {
+    let mut app = App::new("prog")
+        .arg(Arg::with_name("input").required(true))
+        .arg(Arg::with_name("opt").long("opt").takes_value(true));
+    let mut usage = vec![];
+    app.write_usage(&mut usage).unwrap();
+    let usage = String::from_utf8(usage).unwrap();
+    assert_eq!(usage, "USAGE:\n    prog [OPTIONS] <input>\n");
+
+    let mut help = vec![];
+    app.write_help(&mut help).unwrap();
+    assert!(String::from_utf8(help).unwrap().contains(&*usage));
+}
+
+#[test]
+fn gen_help_matches_printed_help() {
+    let app = App::new("prog")
+        .version("1.0")
+        .author("Someone")
+        .about("does things")
+        .arg(Arg::with_name("input").help("the input"))
+        .arg(Arg::with_name("opt").long("opt").takes_value(true).help("an option"));
+    let help = app.gen_help();
+    assert!(help.starts_with("prog 1.0\nSomeone\ndoes things\n\nUSAGE:\n"));
+    assert!(test::compare_output(app, "prog -h", &*help, false));
+}
+
+#[test]
+fn before_and_after_help_wrap_help() {
+    let help = App::new("prog")
+        .version("1.0")
+        .before_help("NOTE: read this first")
+        .after_help("EXAMPLES:\n    prog --verbose\n    prog input.txt")
+        .arg(Arg::with_name("input").help("the input"))
+        .gen_help();
+    assert!(help.starts_with("NOTE: read this first\n\nprog 1.0\n"), "{}", help);
+    assert!(help.ends_with("\n\nEXAMPLES:\n    prog --verbose\n    prog input.txt"), "{}", help);
+}
+
+#[test]
+fn custom_section_headings() {
+    let app = App::new("prog")
+        .flags_heading("Switches")
+        .options_heading("Settings")
+        .positionals_heading("Arguments")
+        .arg(Arg::with_name("input").help("the input"))
+        .arg(Arg::with_name("opt").long("opt").takes_value(true).help("an option"));
+    let help = app.gen_help();
+    assert!(help.contains("\nSwitches:\n    -h, --help"), "{}", help);
+    assert!(help.contains("\nSettings:\n        --opt <opt>"), "{}", help);
+    assert!(help.contains("\nArguments:\n    <input>"), "{}", help);
+    assert!(!help.contains("FLAGS:"));
+
+    let m = app.get_matches_from(vec!["prog", "--opt", "val", "in"]);
+    assert_eq!(m.value_of("opt"), Some("val"));
+    assert_eq!(m.value_of("input"), Some("in"));
+}
+
+#[test]
+fn after_help_highlights_examples() {
+    let app = || {
+        App::new("prog")
+            .setting(AppSettings::ColoredHelp)
+            .after_help("EXAMPLES:\n    $ prog --verbose\n    not an example")
+            .arg(Arg::with_name("verbose").long("verbose"))
+    };
+
+    let help = app().setting(AppSettings::ColorAlways).gen_help();
+    assert!(
+        help.ends_with("\n\nEXAMPLES:\n&a    $ prog --verbose&f\n    not an example"),
+        "{:?}",
+        help
+    );
+
+    let help = app().setting(AppSettings::ColorNever).gen_help();
+    assert!(
+        help.ends_with("\n\nEXAMPLES:\n    $ prog --verbose\n    not an example"),
+        "{:?}",
+        help
+    );
+}
@@ -20,3 +20,12 @@ fn unique_arg_longs() {
     App::new("some")
         .args(&[Arg::with_name("arg1").long("long"), Arg::with_name("arg2").long("long")]);
 }
+
+#[test]
+#[should_panic]
+fn unique_arg_extra_shorts() {
+    App::new("some").args(&[
+        Arg::with_name("arg1").shorts(&['a', 'b']),
+        Arg::with_name("arg2").shorts(&['c', 'b']),
+    ]);
+}
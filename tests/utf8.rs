@@ -4,7 +4,7 @@ extern crate clap;
 
 use std::ffi::OsString;
 use std::os::unix::ffi::OsStringExt;
-use clap::{App, Arg, AppSettings, ErrorKind};
+use clap::{App, Arg, AppSettings, ErrorKind, SubCommand};
 
 #[test]
 fn invalid_utf8_strict_positional() {
@@ -221,3 +221,27 @@ fn invalid_utf8_option_long_equals() {
     assert!(m.is_present("arg"));
     assert_eq!(&*m.value_of_os("arg").unwrap(), &*OsString::from_vec(vec![0xe9]));
 }
+
+#[test]
+fn invalid_utf8_bin_name() {
+    let m = App::new("bad_utf8")
+        .arg(Arg::from_usage("<arg> 'some arg'"))
+        .get_matches_from_safe(vec![OsString::from_vec(vec![b'/', 0xe9]),
+                                    OsString::from("value")]);
+    assert!(m.is_ok());
+    assert_eq!(m.unwrap().value_of("arg"), Some("value"));
+}
+
+#[test]
+fn invalid_utf8_unknown_args() {
+    let app = App::new("bad_utf8")
+        .arg(Arg::from_usage("-a, --arg 'some flag'"))
+        .subcommand(SubCommand::with_name("sub"));
+
+    for bad in vec![vec![0xe9], vec![b'-', b'-', 0xe9], vec![b'-', 0xe9]] {
+        let m = app.clone().get_matches_from_safe(vec![OsString::from(""),
+                                                       OsString::from_vec(bad),
+                                                       OsString::from_vec(vec![0xe9])]);
+        assert!(m.is_err());
+    }
+}
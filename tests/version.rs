@@ -56,3 +56,33 @@ fn override_ver() {
     assert!(m.is_ok());
     assert!(m.unwrap().is_present("version"));
 }
+
+#[test]
+fn version_short_with_custom_help_short() {
+    let app = App::new("test")
+        .version("1.3")
+        .arg(Arg::from_usage("-h, --host [host] 'some host'"));
+
+    let m = app.clone().get_matches_from_safe(vec!["test", "-V"]);
+    assert!(m.is_err());
+    assert_eq!(m.unwrap_err().kind, ErrorKind::VersionDisplayed);
+
+    let m = app.get_matches_from_safe(vec!["test", "-h", "localhost"]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    assert_eq!(m.unwrap().value_of("host"), Some("localhost"));
+}
+
+#[test]
+fn version_short_custom_char() {
+    let app = App::new("test")
+        .version("1.3")
+        .version_short("v")
+        .arg(Arg::from_usage("-V, --verbose 'be loud'"));
+
+    let m = app.clone().get_matches_from_safe(vec!["test", "-v"]);
+    assert_eq!(m.unwrap_err().kind, ErrorKind::VersionDisplayed);
+
+    let m = app.get_matches_from_safe(vec!["test", "-V"]);
+    assert!(m.is_ok(), "{:?}", m.unwrap_err().kind);
+    assert!(m.unwrap().is_present("verbose"));
+}